@@ -0,0 +1,250 @@
+//! Concurrent downloads with per-host rate limiting and retries.
+
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc, Mutex,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use rand::Rng;
+use reqwest::Url;
+
+use crate::{
+    error::{Error, Result},
+    Downloader, Puzzle, PuzzleRef,
+};
+
+/// Politeness settings for a batch download.
+#[derive(Clone, Debug)]
+pub struct BatchOptions {
+    /// The number of worker threads.
+    pub jobs: usize,
+    /// The maximum number of requests per second sent to any one host; see
+    /// [`request_interval`].
+    pub rate: Option<f64>,
+    /// The upper bound of a random delay added before each request.
+    pub jitter: Duration,
+    /// How to retry downloads which fail with a transient error.
+    pub retry: RetryPolicy,
+}
+
+impl Default for BatchOptions {
+    fn default() -> Self {
+        Self {
+            jobs: 4,
+            rate: Some(2.0),
+            jitter: Duration::from_millis(250),
+            retry: RetryPolicy::default(),
+        }
+    }
+}
+
+/// Exponential backoff for transient failures; see [`Error::is_transient`].
+///
+/// [`Error::is_transient`]: crate::Error::is_transient
+#[derive(Clone, Debug)]
+pub struct RetryPolicy {
+    /// The number of retries after the first attempt.
+    pub retries: u32,
+    /// The delay before the first retry, doubled for each retry after that.
+    pub base_delay: Duration,
+    /// The longest delay between two attempts.
+    pub max_delay: Duration,
+}
+
+impl RetryPolicy {
+    /// The delay before retry number `retry` (counting from zero).
+    pub fn delay(&self, retry: u32) -> Duration {
+        self.base_delay
+            .checked_mul(2u32.saturating_pow(retry))
+            .map_or(self.max_delay, |delay| delay.min(self.max_delay))
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            retries: 3,
+            base_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(30),
+        }
+    }
+}
+
+/// The time between two requests sent at `rate` requests per second, or `None` for a rate
+/// of zero, which means no limit.
+///
+/// Fails for negative and non-finite rates, and for rates so low that the interval does not
+/// fit a [`Duration`].
+pub fn request_interval(rate: f64) -> Result<Option<Duration>> {
+    if rate == 0.0 {
+        return Ok(None);
+    }
+    match Duration::try_from_secs_f64(1.0 / rate) {
+        Ok(interval) if rate.is_finite() => Ok(Some(interval)),
+        _ => Err(Error::InvalidInput(format!(
+            "rate out of range: {:?}",
+            rate
+        ))),
+    }
+}
+
+/// Download every puzzle in `puzzles` using a pool of worker threads.
+///
+/// Each download is retried according to `options.retry` while it fails with a transient
+/// error. Fails before downloading anything if `options.rate` is out of range.
+///
+/// Results are returned in the same order as `puzzles`. `progress` is called on the calling
+/// thread as each download completes, in completion order.
+pub fn download_all(
+    downloader: &Downloader,
+    puzzles: &[PuzzleRef],
+    options: &BatchOptions,
+    mut progress: impl FnMut(&PuzzleRef, &Result<Puzzle>),
+) -> Result<Vec<Result<Puzzle>>> {
+    let limiter = RateLimiter::new(options.rate)?;
+    let next = AtomicUsize::new(0);
+    let (tx, rx) = mpsc::channel();
+    let mut results: Vec<_> = puzzles.iter().map(|_| None).collect();
+
+    thread::scope(|scope| {
+        for _ in 0..options.jobs.clamp(1, puzzles.len().max(1)) {
+            let tx = tx.clone();
+            let (limiter, next) = (&limiter, &next);
+            scope.spawn(move || {
+                let mut rng = rand::thread_rng();
+                loop {
+                    let idx = next.fetch_add(1, Ordering::Relaxed);
+                    let puzzle = match puzzles.get(idx) {
+                        Some(puzzle) => puzzle,
+                        None => break,
+                    };
+
+                    let mut retry = 0;
+                    let result = loop {
+                        limiter.wait(&puzzle.url());
+                        if options.jitter > Duration::ZERO {
+                            thread::sleep(rng.gen_range(Duration::ZERO..=options.jitter));
+                        }
+
+                        match downloader.download(puzzle) {
+                            Err(e) if e.is_transient() && retry < options.retry.retries => {
+                                thread::sleep(options.retry.delay(retry));
+                                retry += 1;
+                            }
+                            result => break result,
+                        }
+                    };
+
+                    if tx.send((idx, result)).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        drop(tx);
+
+        for (idx, result) in rx {
+            progress(&puzzles[idx], &result);
+            results[idx] = Some(result);
+        }
+    });
+
+    Ok(results
+        .into_iter()
+        .map(|result| result.expect("every puzzle is downloaded exactly once"))
+        .collect())
+}
+
+/// Spaces out requests to each host so that no host sees more than `rate` per second.
+struct RateLimiter {
+    interval: Option<Duration>,
+    next: Mutex<HashMap<String, Instant>>,
+}
+
+impl RateLimiter {
+    fn new(rate: Option<f64>) -> Result<Self> {
+        Ok(Self {
+            interval: rate.map(request_interval).transpose()?.flatten(),
+            next: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// Block until a request to the host of `url` is allowed.
+    fn wait(&self, url: &str) {
+        let interval = match self.interval {
+            Some(interval) => interval,
+            None => return,
+        };
+
+        let host = Url::parse(url)
+            .ok()
+            .and_then(|url| url.host_str().map(String::from))
+            .unwrap_or_default();
+
+        let slot = {
+            let mut next = self.next.lock().unwrap();
+            let now = Instant::now();
+            let slot = next.get(&host).map_or(now, |&next| next.max(now));
+            next.insert(host, slot + interval);
+            slot
+        };
+
+        let now = Instant::now();
+        if slot > now {
+            thread::sleep(slot - now);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::{Duration, Instant};
+
+    use super::{request_interval, RateLimiter, RetryPolicy};
+
+    #[test]
+    fn retry_delay_backs_off() {
+        let policy = RetryPolicy {
+            retries: 10,
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(1),
+        };
+
+        assert_eq!(policy.delay(0), Duration::from_millis(100));
+        assert_eq!(policy.delay(3), Duration::from_millis(800));
+        assert_eq!(policy.delay(4), Duration::from_secs(1));
+        assert_eq!(policy.delay(40), Duration::from_secs(1));
+    }
+
+    #[test]
+    fn rate_limiter_spaces_requests_per_host() {
+        let limiter = RateLimiter::new(Some(20.0)).unwrap();
+        let start = Instant::now();
+
+        limiter.wait("https://a.example/1");
+        limiter.wait("https://b.example/1");
+        assert!(start.elapsed() < Duration::from_millis(50));
+
+        limiter.wait("https://a.example/2");
+        limiter.wait("https://a.example/3");
+        assert!(start.elapsed() >= Duration::from_millis(100));
+    }
+
+    #[test]
+    fn request_interval_rejects_unusable_rates() {
+        assert_eq!(request_interval(0.0).unwrap(), None);
+        assert_eq!(
+            request_interval(4.0).unwrap(),
+            Some(Duration::from_millis(250))
+        );
+        for rate in [1e-300, -1.0, f64::INFINITY, f64::NAN] {
+            assert!(request_interval(rate).is_err());
+        }
+        assert!(RateLimiter::new(Some(1e-20)).is_err());
+    }
+}
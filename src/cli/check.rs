@@ -0,0 +1,34 @@
+use clap::Clap;
+
+use websudoku::{Error, Result};
+
+use super::source::Source;
+
+/// Check that puzzles have exactly one solution, matching websudoku's own
+#[derive(Clap, Clone, Debug)]
+pub struct Check {
+    /// Stop counting solutions after this many
+    #[clap(long, default_value = "2")]
+    limit: usize,
+
+    #[clap(flatten)]
+    source: Source,
+}
+
+impl Check {
+    pub fn run(&self) -> Result<()> {
+        let mut loaded = self.source.load()?;
+
+        loaded.retain(|puzzle| {
+            match puzzle.count_solutions(self.limit.max(2)) {
+                0 => Err(Error::Unsolvable),
+                1 => puzzle.cross_check(),
+                count => Err(Error::MultipleSolutions(count)),
+            }?;
+            println!("{}: unique", puzzle.puzzle_ref());
+            Ok(())
+        });
+
+        loaded.finish()
+    }
+}
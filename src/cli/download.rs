@@ -0,0 +1,120 @@
+use std::io::{self, Write};
+
+use clap::Clap;
+
+use websudoku::{format::Options, render::Layout, Format, Output, Puzzle};
+
+use super::open;
+
+// Where and how downloaded puzzles are written.
+#[derive(Clap, Clone, Debug)]
+pub struct Destination {
+    /// The output format: csv, html, json, latex, line, pdf, png, svg or text
+    #[clap(short = 'F', long, default_value = "csv")]
+    format: Format,
+
+    /// Include the solution: the line format appends it to the givens, the csv format
+    /// writes it in full with the givens in brackets, and the latex format adds a solution
+    /// grid after the puzzles
+    #[clap(long, alias = "solution")]
+    with_solution: bool,
+
+    /// Write a header row naming the columns (csv format only)
+    #[clap(long)]
+    header: bool,
+
+    /// Write a complete document rather than bare sudoku environments (latex format only)
+    #[clap(long)]
+    standalone: bool,
+
+    /// The placeholder written for blank cells, such as . or 0 (csv format only; default:
+    /// an empty field)
+    #[clap(long)]
+    blank: Option<String>,
+
+    /// Distinguish givens from blanks with ANSI colors (text format only)
+    #[clap(long)]
+    color: bool,
+
+    /// Draw givens in bold when including the solution (svg and png formats only)
+    #[clap(long)]
+    bold_givens: bool,
+
+    /// The width and height of a cell, in pixels at 96 dpi (svg and png formats only)
+    #[clap(long, default_value = "40")]
+    cell_size: f32,
+
+    /// The font family used for digits (svg format only)
+    #[clap(long, default_value = "sans-serif")]
+    font: String,
+
+    /// The resolution of the image, in dots per inch (png format only)
+    #[clap(long, default_value = "96")]
+    dpi: f32,
+
+    /// The number of puzzles on each page (pdf format only)
+    #[clap(long, default_value = "1", possible_values = &["1", "2", "4", "6"])]
+    per_page: usize,
+
+    /// Overwrite output files if they already exist
+    #[clap(short, long)]
+    force: bool,
+
+    /// The path of the output file. By default, each puzzle is written to
+    /// "<difficulty> <puzzle>.<format>", where puzzle is the puzzle's identifier. Use - to
+    /// write to stdout, or a directory to write the default file names into that directory.
+    /// When several puzzles are written to stdout or a single file, they share one stream.
+    /// The path is only ever given with this option: every other argument names a puzzle.
+    #[clap(short, long)]
+    output: Option<String>,
+}
+
+impl Destination {
+    pub fn write_puzzles(&self, puzzles: &[Puzzle]) -> io::Result<()> {
+        let options = Options {
+            with_solution: self.with_solution,
+            header: self.header,
+            standalone: self.standalone,
+            blank: self.blank.clone().unwrap_or_default(),
+            color: self.color,
+            bold_givens: self.bold_givens,
+            per_page: self.per_page,
+            dpi: self.dpi,
+            layout: Layout {
+                cell_size: self.cell_size,
+                margin: self.cell_size / 4.0,
+                thin_line: self.cell_size / 40.0,
+                thick_line: self.cell_size * 3.0 / 40.0,
+                font_family: self.font.clone(),
+                ..Layout::default()
+            },
+        };
+        let extension = self.format.extension();
+        let path = self.output.as_deref();
+
+        match puzzles {
+            [] => Ok(()),
+            [puzzle] => {
+                let output = Output::resolve(path, &puzzle.file_name(extension));
+                let mut w = open(&output, self.force)?;
+                self.format.write(puzzle, &options, &mut w)?;
+                w.flush()
+            }
+            puzzles if Output::is_shared(path) => {
+                let output = Output::resolve(path, "");
+                let mut w = open(&output, self.force)?;
+                self.format.write_many(puzzles, &options, &mut w)?;
+                w.flush()
+            }
+            puzzles => {
+                for puzzle in puzzles {
+                    let output = Output::resolve(path, &puzzle.file_name(extension));
+                    let mut w = open(&output, self.force)?;
+                    self.format.write(puzzle, &options, &mut w)?;
+                    w.flush()?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
@@ -0,0 +1,54 @@
+use std::io::Write;
+
+use clap::Clap;
+
+use websudoku::{
+    logic::{self, explain::Format},
+    Output, Result,
+};
+
+use super::{open, source::Source};
+
+/// Explain step by step how to solve puzzles by hand
+#[derive(Clap, Clone, Debug)]
+pub struct Explain {
+    /// The output format: text, markdown or json
+    #[clap(short = 'F', long, default_value = "text")]
+    format: Format,
+
+    /// Overwrite the output file if it already exists
+    #[clap(short, long)]
+    force: bool,
+
+    /// The path of the output file (default: stdout)
+    #[clap(short, long)]
+    output: Option<String>,
+
+    #[clap(flatten)]
+    source: Source,
+}
+
+impl Explain {
+    pub fn run(&self) -> Result<()> {
+        let mut loaded = self.source.load()?;
+
+        let mut solutions = Vec::new();
+        loaded.retain(|puzzle| {
+            solutions.push(logic::solve(&puzzle.givens())?);
+            Ok(())
+        });
+        let walkthroughs: Vec<_> = loaded.puzzles.iter().zip(&solutions).collect();
+
+        let output = Output::resolve(Some(self.output.as_deref().unwrap_or("-")), "");
+        let mut w = open(&output, self.force)?;
+        match &walkthroughs[..] {
+            [(puzzle, solution)] => self.format.write(puzzle, solution, &mut w)?,
+            walkthroughs => self
+                .format
+                .write_many(walkthroughs.iter().copied(), &mut w)?,
+        }
+        w.flush()?;
+
+        loaded.finish()
+    }
+}
@@ -0,0 +1,47 @@
+use clap::Clap;
+
+use websudoku::{grid, logic, Result};
+
+use super::{read_grid, source::Source};
+
+/// Suggest the next step for a partially solved puzzle
+#[derive(Clap, Clone, Debug)]
+pub struct Hint {
+    /// The player's grid, in the layout written by the csv format. Use - to read from stdin.
+    #[clap(short, long)]
+    grid: String,
+
+    #[clap(flatten)]
+    source: Source,
+}
+
+impl Hint {
+    pub fn run(&self) -> Result<()> {
+        let puzzle = self.source.load()?.single("hint")?;
+        let entries = read_grid(&self.grid)?;
+
+        // Wrong entries come first: any deduction built on them would be unsound.
+        let wrong: Vec<usize> = (0..grid::CELLS)
+            .filter(|&cell| entries[cell] != 0 && entries[cell] != puzzle.solution[cell])
+            .collect();
+        if !wrong.is_empty() {
+            for &cell in &wrong {
+                println!("wrong: {} holds {}", grid::cell_name(cell), entries[cell]);
+            }
+            println!("remove the wrong entries, then ask for another hint");
+            return Ok(());
+        }
+
+        // Givens the player left blank are filled back in before looking for a step.
+        let current: Vec<u8> = entries
+            .iter()
+            .zip(puzzle.givens())
+            .map(|(&entry, given)| entry.max(given))
+            .collect();
+        match logic::next_step(&current)? {
+            Some(step) => println!("next: {}", step),
+            None => println!("solved"),
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,51 @@
+pub mod check;
+pub mod download;
+pub mod explain;
+pub mod hint;
+pub mod play;
+pub mod rate;
+pub mod show;
+pub mod source;
+pub mod verify;
+
+use std::{
+    fs,
+    io::{self, Read, Write},
+};
+
+use websudoku::{format::csv, Error, Output, Result};
+
+/// Read a file, or stdin if `path` is `-`.
+pub fn read_input(path: &str) -> io::Result<String> {
+    match path {
+        "-" => {
+            let mut content = String::new();
+            io::stdin().read_to_string(&mut content)?;
+            Ok(content)
+        }
+        path => fs::read_to_string(path),
+    }
+}
+
+/// Open an output, pointing at `--force` when it would overwrite a file.
+pub fn open(output: &Output, force: bool) -> io::Result<Box<dyn Write>> {
+    output.open(force).map_err(|e| match e.kind() {
+        io::ErrorKind::AlreadyExists => {
+            io::Error::new(e.kind(), format!("{} (use --force to overwrite)", e))
+        }
+        _ => e,
+    })
+}
+
+/// Read a single csv grid from a file, or stdin if `path` is `-`, with `0` for blank cells.
+pub fn read_grid(path: &str) -> Result<Vec<u8>> {
+    csv::read_grid(&read_input(path)?).map_err(|e| in_file(path, e))
+}
+
+/// Name the file an invalid input came from.
+pub fn in_file(path: &str, e: Error) -> Error {
+    match e {
+        Error::InvalidInput(message) => Error::InvalidInput(format!("{}: {}", path, message)),
+        e => e,
+    }
+}
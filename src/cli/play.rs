@@ -0,0 +1,350 @@
+use std::{
+    io::{self, IsTerminal, Stdout, Write},
+    time::{Duration, Instant},
+};
+
+use clap::Clap;
+use crossterm::{
+    cursor::{Hide, MoveTo, Show},
+    event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    queue,
+    style::{Attribute, Color, Print, PrintStyledContent, StyledContent, Stylize},
+    terminal::{self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen},
+};
+
+use websudoku::{grid, play::Game, Error, Result};
+
+use super::source::Source;
+
+/// The width of the grid when each cell is drawn 5 columns wide and 3 rows tall, with room
+/// for pencil marks, and the height including the lines around it.
+const LARGE: (u16, u16) = (55, 35);
+
+const HELP: &str = "arrows move  1-9 digit  p pencil  0 clear  u undo  r redo  c check  q quit";
+
+/// Solve a puzzle in the terminal
+#[derive(Clap, Clone, Debug)]
+pub struct Play {
+    #[clap(flatten)]
+    source: Source,
+}
+
+impl Play {
+    pub fn run(&self) -> Result<()> {
+        let puzzle = self.source.load()?.single("play")?;
+        if !io::stdout().is_terminal() {
+            return Err(Error::InvalidInput(String::from(
+                "play needs a terminal to draw in",
+            )));
+        }
+
+        let mut session = Session::new(Game::new(puzzle)?);
+        {
+            let mut screen = Screen::enter()?;
+            session.run(&mut screen.stdout)?;
+        }
+
+        let game = &session.game;
+        let elapsed = clock(session.elapsed());
+        if game.is_solved() {
+            println!("{}: solved in {}", game.puzzle().puzzle_ref(), elapsed);
+        } else {
+            let blanks = game.puzzle().mask.iter().filter(|&&blank| blank).count();
+            let missing = game.check().missing.len();
+            println!(
+                "{}: {} of {} cells filled after {}",
+                game.puzzle().puzzle_ref(),
+                blanks - missing,
+                blanks,
+                elapsed
+            );
+        }
+        Ok(())
+    }
+}
+
+/// The terminal in raw mode on the alternate screen, restored when dropped.
+struct Screen {
+    stdout: Stdout,
+}
+
+impl Screen {
+    fn enter() -> io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        let mut stdout = io::stdout();
+        queue!(stdout, EnterAlternateScreen, Hide)?;
+        stdout.flush()?;
+        Ok(Self { stdout })
+    }
+}
+
+impl Drop for Screen {
+    fn drop(&mut self) {
+        // Nothing more can be done if the terminal cannot be restored.
+        let _ = queue!(self.stdout, Show, LeaveAlternateScreen);
+        let _ = self.stdout.flush();
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+/// A game in progress, with everything about it that is not part of the puzzle.
+struct Session {
+    game: Game,
+    pencil: bool,
+    /// The cells found wrong by the last check, until the next edit.
+    wrong: Vec<bool>,
+    message: String,
+    started: Instant,
+    finished: Option<Instant>,
+}
+
+impl Session {
+    fn new(game: Game) -> Self {
+        Self {
+            game,
+            pencil: false,
+            wrong: vec![false; grid::CELLS],
+            message: String::new(),
+            started: Instant::now(),
+            finished: None,
+        }
+    }
+
+    fn elapsed(&self) -> Duration {
+        self.finished.unwrap_or_else(Instant::now) - self.started
+    }
+
+    /// Draw the game and handle keys until the player quits.
+    fn run(&mut self, w: &mut impl Write) -> io::Result<()> {
+        queue!(w, Clear(ClearType::All))?;
+        loop {
+            self.draw(w)?;
+
+            // Wake up at least once a second to keep the timer running.
+            if !event::poll(Duration::from_millis(250))? {
+                continue;
+            }
+            match event::read()? {
+                Event::Key(key) if key.kind != KeyEventKind::Release && !self.handle(key) => {
+                    return Ok(())
+                }
+                Event::Resize(..) => queue!(w, Clear(ClearType::All))?,
+                _ => {}
+            }
+        }
+    }
+
+    /// Act on a key, returning whether to keep playing.
+    fn handle(&mut self, key: KeyEvent) -> bool {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        self.message.clear();
+
+        match key.code {
+            KeyCode::Char('c') if ctrl => return false,
+            KeyCode::Char('q') | KeyCode::Esc => return false,
+
+            KeyCode::Up | KeyCode::Char('k') => self.game.move_cursor(-1, 0),
+            KeyCode::Down | KeyCode::Char('j') => self.game.move_cursor(1, 0),
+            KeyCode::Left | KeyCode::Char('h') => self.game.move_cursor(0, -1),
+            KeyCode::Right | KeyCode::Char('l') => self.game.move_cursor(0, 1),
+
+            // Moves are allowed once the puzzle is solved, but nothing else.
+            _ if self.finished.is_some() => {}
+
+            KeyCode::Char('p') => self.pencil = !self.pencil,
+            KeyCode::Char(c @ '1'..='9') => {
+                let digit = c as u8 - b'0';
+                let changed = if self.pencil {
+                    self.game.toggle_mark(digit)
+                } else {
+                    self.game.enter(digit)
+                };
+                self.edited(changed);
+            }
+            KeyCode::Char('0') | KeyCode::Char('.') | KeyCode::Backspace | KeyCode::Delete => {
+                let changed = self.game.clear();
+                self.edited(changed);
+            }
+            KeyCode::Char('z') if ctrl => self.undo(),
+            KeyCode::Char('y') if ctrl => self.redo(),
+            KeyCode::Char('u') => self.undo(),
+            KeyCode::Char('r') => self.redo(),
+            KeyCode::Char('c') => self.check(),
+            _ => {}
+        }
+        true
+    }
+
+    fn undo(&mut self) {
+        let changed = self.game.undo();
+        if !changed {
+            self.message = String::from("nothing to undo");
+        }
+        self.edited(changed);
+    }
+
+    fn redo(&mut self) {
+        let changed = self.game.redo();
+        if !changed {
+            self.message = String::from("nothing to redo");
+        }
+        self.edited(changed);
+    }
+
+    /// Forget the last check after a change, and stop the clock once the puzzle is solved.
+    fn edited(&mut self, changed: bool) {
+        if !changed {
+            return;
+        }
+        self.wrong = vec![false; grid::CELLS];
+        if self.game.is_solved() {
+            self.finished = Some(Instant::now());
+            self.message = format!("solved in {}!", clock(self.elapsed()));
+        }
+    }
+
+    fn check(&mut self) {
+        let check = self.game.check();
+        self.wrong = vec![false; grid::CELLS];
+        for mistake in &check.wrong {
+            self.wrong[mistake.cell] = true;
+        }
+        self.message = match check.wrong.len() {
+            0 => format!("no mistakes so far; {} left to fill", check.missing.len()),
+            1 => format!("1 wrong cell; {} left to fill", check.missing.len()),
+            wrong => format!(
+                "{} wrong cells; {} left to fill",
+                wrong,
+                check.missing.len()
+            ),
+        };
+    }
+
+    fn draw(&self, w: &mut impl Write) -> io::Result<()> {
+        let (columns, rows) = terminal::size()?;
+        let large = columns >= LARGE.0 && rows >= LARGE.1;
+        let height = if large { 3 } else { 1 };
+        let conflicts = self.game.conflicts();
+
+        let border = |left: &str, middle: &str, right: &str| {
+            // Large cells are separated by thin lines within each box.
+            let box_width = "─".repeat(if large { 17 } else { 9 });
+            format!("{0}{1}{2}{1}{2}{1}{3}", left, box_width, middle, right)
+        };
+
+        let mut y = 0;
+        line(w, &mut y, &self.game.puzzle().puzzle_ref().to_string())?;
+        line(w, &mut y, &border("┌", "┬", "┐"))?;
+        for row in 0..9 {
+            if row == 3 || row == 6 {
+                line(w, &mut y, &border("├", "┼", "┤"))?;
+            }
+            for part in 0..height {
+                queue!(w, MoveTo(0, y), Print("│"))?;
+                for column in 0..9 {
+                    let cell = row * 9 + column;
+                    let text = self.cell_text(cell, part, large);
+                    queue!(w, PrintStyledContent(self.style(cell, &conflicts, text)))?;
+                    if column % 3 == 2 {
+                        queue!(w, Print("│"))?;
+                    } else if large {
+                        queue!(w, PrintStyledContent("│".with(Color::DarkGrey)))?;
+                    }
+                }
+                queue!(w, Clear(ClearType::UntilNewLine))?;
+                y += 1;
+            }
+        }
+        line(w, &mut y, &border("└", "┴", "┘"))?;
+
+        let mut status = format!(
+            "{}   pencil {}",
+            clock(self.elapsed()),
+            if self.pencil { "on" } else { "off" }
+        );
+        if !large {
+            let marks: String = (1..=9)
+                .filter(|&digit| self.game.has_mark(self.game.cursor(), digit))
+                .map(|digit| char::from(b'0' + digit))
+                .collect();
+            if !marks.is_empty() {
+                status.push_str(&format!("   marks {}", marks));
+            }
+        }
+        line(w, &mut y, &status)?;
+        line(w, &mut y, &self.message)?;
+        line(w, &mut y, HELP)?;
+        queue!(w, Clear(ClearType::FromCursorDown))?;
+        w.flush()
+    }
+
+    /// One line of a cell's text: its digit in the middle line, or its pencil marks laid out
+    /// like a keypad when drawing large cells.
+    fn cell_text(&self, cell: usize, part: usize, large: bool) -> String {
+        let digit = self.game.entries()[cell];
+        if !large {
+            let marked = (1..=9).any(|digit| self.game.has_mark(cell, digit));
+            return match digit {
+                0 if marked => String::from(" · "),
+                0 => String::from("   "),
+                digit => format!(" {} ", digit),
+            };
+        }
+
+        if digit != 0 {
+            return match part {
+                1 => format!("  {}  ", digit),
+                _ => String::from("     "),
+            };
+        }
+        let marks: Vec<String> = (part as u8 * 3 + 1..=part as u8 * 3 + 3)
+            .map(|digit| match self.game.has_mark(cell, digit) {
+                true => digit.to_string(),
+                false => String::from(" "),
+            })
+            .collect();
+        marks.join(" ")
+    }
+
+    fn style(&self, cell: usize, conflicts: &[bool], text: String) -> StyledContent<String> {
+        let mut styled = text.stylize();
+        if self.game.is_given(cell) {
+            styled = styled.attribute(Attribute::Bold);
+        } else if self.game.entries()[cell] == 0 {
+            styled = styled.with(Color::DarkGrey);
+        } else {
+            styled = styled.with(Color::Cyan);
+        }
+
+        if self.wrong[cell] {
+            styled = styled.with(Color::White).on(Color::DarkRed);
+        } else if conflicts[cell] {
+            styled = styled.with(Color::Red);
+        }
+        if cell == self.game.cursor() {
+            styled = styled.attribute(Attribute::Reverse);
+        }
+        styled
+    }
+}
+
+/// Write a line of the screen, erasing whatever was left of the last frame.
+fn line(w: &mut impl Write, y: &mut u16, text: &str) -> io::Result<()> {
+    queue!(
+        w,
+        MoveTo(0, *y),
+        Print(text),
+        Clear(ClearType::UntilNewLine)
+    )?;
+    *y += 1;
+    Ok(())
+}
+
+/// Format a duration as minutes and seconds, or hours, minutes and seconds.
+fn clock(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    match seconds / 3600 {
+        0 => format!("{}:{:02}", seconds / 60, seconds % 60),
+        hours => format!("{}:{:02}:{:02}", hours, seconds / 60 % 60, seconds % 60),
+    }
+}
@@ -0,0 +1,31 @@
+use clap::Clap;
+
+use websudoku::{logic, Result};
+
+use super::source::Source;
+
+/// Rate puzzles by the hardest technique needed to solve them by hand
+#[derive(Clap, Clone, Debug)]
+pub struct Rate {
+    #[clap(flatten)]
+    source: Source,
+}
+
+impl Rate {
+    pub fn run(&self) -> Result<()> {
+        let mut loaded = self.source.load()?;
+
+        loaded.retain(|puzzle| {
+            let solution = logic::solve(&puzzle.givens())?;
+            println!(
+                "{}: {}, labelled {}",
+                puzzle.puzzle_ref(),
+                solution.rating(),
+                puzzle.difficulty
+            );
+            Ok(())
+        });
+
+        loaded.finish()
+    }
+}
@@ -0,0 +1,45 @@
+use std::{
+    env,
+    io::{self, IsTerminal, Write},
+};
+
+use clap::Clap;
+
+use websudoku::{format::Options, Format, Result};
+
+use super::source::Source;
+
+/// Draw puzzles in the terminal
+#[derive(Clap, Clone, Debug)]
+pub struct Show {
+    /// Fill in the blanks from the solution
+    #[clap(long, alias = "solution")]
+    with_solution: bool,
+
+    /// Never use colors. By default, givens and blanks are colored when writing to a
+    /// terminal and NO_COLOR is not set.
+    #[clap(long)]
+    no_color: bool,
+
+    #[clap(flatten)]
+    source: Source,
+}
+
+impl Show {
+    pub fn run(&self) -> Result<()> {
+        let loaded = self.source.load()?;
+
+        let stdout = io::stdout();
+        let options = Options {
+            with_solution: self.with_solution,
+            color: !self.no_color && env::var_os("NO_COLOR").is_none() && stdout.is_terminal(),
+            ..Options::default()
+        };
+
+        let mut w = stdout.lock();
+        Format::Text.write_many(&loaded.puzzles, &options, &mut w)?;
+        w.flush()?;
+
+        loaded.finish()
+    }
+}
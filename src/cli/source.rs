@@ -0,0 +1,321 @@
+use std::{path::Path, process, time::Duration};
+
+use clap::Clap;
+use indicatif::{ProgressBar, ProgressStyle};
+
+use websudoku::{
+    batch::{self, BatchOptions, RetryPolicy},
+    Difficulty, Downloader, Error, Field, Puzzle, PuzzleExtractor, PuzzleRef, Resolver, Result,
+};
+
+use super::{read_grid, read_input};
+
+// Where puzzles come from, and how politely to download them. (Not a doc comment: clap
+// would use it as the about text of every command flattening these options.)
+#[derive(Clap, Clone, Debug)]
+pub struct Source {
+    /// The difficulty level of the puzzles (default: EVIL)
+    #[clap(short, long)]
+    difficulty: Option<Difficulty>,
+
+    /// Read additional puzzle urls, identifiers or ranges from a file, one or more per line.
+    /// Use - to read from stdin.
+    #[clap(long)]
+    from_file: Option<String>,
+
+    /// Extract a puzzle from a saved websudoku page instead of downloading it. Use - to read
+    /// the page from stdin. May be given more than once.
+    #[clap(long, number_of_values = 1)]
+    html: Vec<String>,
+
+    /// Read puzzles from csv grids saved by this tool instead of downloading them, solving
+    /// each grid for its solution. Each file holds one grid and is named for its puzzle, as
+    /// in "Evil 7042100266.csv" or "7042100266.csv". May be given more than once.
+    #[clap(long, number_of_values = 1)]
+    csv: Vec<String>,
+
+    /// The number of puzzles to download at once
+    #[clap(short, long, default_value = "4")]
+    jobs: usize,
+
+    /// The maximum number of requests per second sent to websudoku (0 for no limit)
+    #[clap(long, default_value = "2", parse(try_from_str = parse_rate))]
+    rate: f64,
+
+    /// The maximum random delay, in milliseconds, added before each request
+    #[clap(long, default_value = "250")]
+    jitter: u64,
+
+    /// The number of times to retry a download after a transient failure
+    #[clap(long, default_value = "3")]
+    retries: u32,
+
+    /// The delay, in milliseconds, before the first retry; doubled for each further retry
+    #[clap(long, default_value = "1000")]
+    backoff: u64,
+
+    /// Solve each puzzle independently and reject any whose solution does not match
+    #[clap(long)]
+    cross_check: bool,
+
+    /// Do not show a progress bar
+    #[clap(short, long)]
+    quiet: bool,
+
+    /// Puzzle urls, identifiers, or ranges of identifiers such as 1000..1100 (exclusive)
+    /// or 1000..=1100 (inclusive)
+    #[clap(required_unless_present_any = &["from-file", "html", "csv"])]
+    puzzles: Vec<String>,
+}
+
+/// Parse a request rate, rejecting any whose interval between requests is not a duration.
+fn parse_rate(s: &str) -> Result<f64, String> {
+    let rate: f64 = s.parse().map_err(|_| format!("not a number: {}", s))?;
+    batch::request_interval(rate).map_err(|e| e.to_string())?;
+    Ok(rate)
+}
+
+/// The puzzles retrieved from a `Source`, along with those which could not be retrieved.
+pub struct Loaded {
+    pub puzzles: Vec<Puzzle>,
+    pub failures: Vec<(String, Error)>,
+}
+
+impl Source {
+    /// Read saved pages and grids, and download every requested puzzle.
+    pub fn load(&self) -> Result<Loaded> {
+        let puzzle_refs = self.read_puzzle_refs()?;
+        let mut loaded = Loaded {
+            puzzles: Vec::new(),
+            failures: Vec::new(),
+        };
+
+        let extractor = PuzzleExtractor::new();
+        for path in &self.html {
+            match self.read_page(&extractor, path) {
+                Ok(puzzle) => loaded.puzzles.push(puzzle),
+                Err(e) => loaded.failures.push((path.clone(), e)),
+            }
+        }
+
+        for path in &self.csv {
+            match self.read_csv(path) {
+                Ok(puzzle) => loaded.puzzles.push(puzzle),
+                Err(e) => loaded.failures.push((path.clone(), e)),
+            }
+        }
+
+        if !puzzle_refs.is_empty() {
+            let results = self.download(&puzzle_refs)?;
+            for (puzzle_ref, result) in puzzle_refs.iter().zip(results) {
+                match result {
+                    Ok(puzzle) => loaded.puzzles.push(puzzle),
+                    Err(e) => loaded.failures.push((puzzle_ref.to_string(), e)),
+                }
+            }
+        }
+
+        if self.cross_check {
+            let mut checked = Vec::new();
+            for puzzle in loaded.puzzles {
+                match puzzle.cross_check() {
+                    Ok(()) => checked.push(puzzle),
+                    Err(e) => loaded.failures.push((puzzle.puzzle_ref().to_string(), e)),
+                }
+            }
+            loaded.puzzles = checked;
+        }
+
+        Ok(loaded)
+    }
+
+    fn download(&self, puzzle_refs: &[PuzzleRef]) -> Result<Vec<Result<Puzzle>>> {
+        let downloader = Downloader::new()?;
+        let batch_options = BatchOptions {
+            jobs: self.jobs,
+            rate: Some(self.rate),
+            jitter: Duration::from_millis(self.jitter),
+            retry: RetryPolicy {
+                retries: self.retries,
+                base_delay: Duration::from_millis(self.backoff),
+                ..RetryPolicy::default()
+            },
+        };
+
+        let progress = if self.quiet || puzzle_refs.len() < 2 {
+            ProgressBar::hidden()
+        } else {
+            ProgressBar::new(puzzle_refs.len() as u64).with_style(
+                ProgressStyle::default_bar()
+                    .template("{bar:40} {pos}/{len} [{elapsed_precise}] {msg}")
+                    .expect("progress template is valid"),
+            )
+        };
+
+        let results = batch::download_all(&downloader, puzzle_refs, &batch_options, |_, _| {
+            progress.inc(1)
+        })?;
+        progress.finish_and_clear();
+
+        Ok(results)
+    }
+
+    /// Extract a puzzle from a saved page, preferring an explicit difficulty over the page's
+    /// own.
+    fn read_page(&self, extractor: &PuzzleExtractor, path: &str) -> Result<Puzzle> {
+        let content = read_input(path)?;
+        let difficulty = self
+            .difficulty
+            .or_else(|| extractor.difficulty(&content))
+            .unwrap_or_default();
+
+        let puzzle = extractor.extract(difficulty, &content)?;
+        puzzle.validate()?;
+        Ok(puzzle)
+    }
+
+    /// Read the grid in a csv file, naming the puzzle after the file.
+    ///
+    /// A file name of the form "<difficulty> <id>" gives both, though an explicit difficulty
+    /// still wins; otherwise the file name is the identifier. Either way the identifier must
+    /// be numeric, since it ends up in urls and file names.
+    fn read_csv(&self, path: &str) -> Result<Puzzle> {
+        let givens = read_grid(path)?;
+
+        let stem = Path::new(path)
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .unwrap_or(path);
+        let (difficulty, id) = match stem.split_once(' ') {
+            Some((difficulty, id)) => match difficulty.parse::<Difficulty>() {
+                Ok(difficulty) => (Some(difficulty), id),
+                Err(_) => (None, stem),
+            },
+            None => (None, stem),
+        };
+        let difficulty = self.difficulty.or(difficulty).unwrap_or_default();
+
+        Puzzle::from_givens(difficulty, id.to_string(), &givens).map_err(|e| match e {
+            Error::MissingField(Field::PuzzleId)
+            | Error::MalformedDigit {
+                field: Field::PuzzleId,
+                ..
+            } => Error::InvalidInput(format!(
+                "{}: no puzzle identifier; save the grid as, say, \"Evil 7042100266.csv\"",
+                path
+            )),
+            e => e,
+        })
+    }
+
+    fn read_puzzle_refs(&self) -> Result<Vec<PuzzleRef>> {
+        let mut specs = self.puzzles.clone();
+        if let Some(path) = &self.from_file {
+            let content = read_input(path)?;
+
+            specs.extend(
+                content
+                    .lines()
+                    .filter(|line| !line.trim_start().starts_with('#'))
+                    .flat_map(str::split_whitespace)
+                    .map(String::from),
+            );
+        }
+
+        let resolver = Resolver::new();
+        let mut puzzle_refs = Vec::new();
+        for spec in &specs {
+            puzzle_refs.extend(resolver.expand(spec, self.difficulty)?);
+        }
+
+        if puzzle_refs.is_empty() && self.html.is_empty() && self.csv.is_empty() {
+            return Err(Error::InvalidInput(String::from("no puzzles to download")));
+        }
+
+        Ok(puzzle_refs)
+    }
+}
+
+impl Loaded {
+    /// The only puzzle, for commands which work on exactly one.
+    pub fn single(self, command: &str) -> Result<Puzzle> {
+        let total = self.puzzles.len() + self.failures.len();
+        if total != 1 {
+            return Err(Error::InvalidInput(format!(
+                "{} takes one puzzle; got {}",
+                command, total
+            )));
+        }
+
+        match self.failures.into_iter().next() {
+            Some((_, e)) => Err(e),
+            None => Ok(self
+                .puzzles
+                .into_iter()
+                .next()
+                .expect("one puzzle was loaded")),
+        }
+    }
+
+    /// Keep the puzzles which pass `check`, moving the rest to the failures so that each
+    /// puzzle is counted once.
+    pub fn retain(&mut self, mut check: impl FnMut(&Puzzle) -> Result<()>) {
+        for puzzle in std::mem::take(&mut self.puzzles) {
+            match check(&puzzle) {
+                Ok(()) => self.puzzles.push(puzzle),
+                Err(e) => self.failures.push((puzzle.puzzle_ref().to_string(), e)),
+            }
+        }
+    }
+
+    /// Report any failures, exiting with the first failure's exit code.
+    ///
+    /// A lone failure is returned as-is so that it is reported like any other error.
+    pub fn finish(mut self) -> Result<()> {
+        let total = self.puzzles.len() + self.failures.len();
+        if self.failures.is_empty() {
+            return Ok(());
+        }
+
+        if total == 1 {
+            let (_, e) = self.failures.remove(0);
+            return Err(e);
+        }
+
+        eprintln!(
+            "retrieved {} of {} puzzles; {} failed:",
+            self.puzzles.len(),
+            total,
+            self.failures.len()
+        );
+        for (source, e) in &self.failures {
+            eprintln!("  {}: {}", source, e);
+        }
+        process::exit(self.failures[0].1.exit_code());
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use websudoku::{Difficulty, Error, PuzzleExtractor};
+
+    use super::Loaded;
+
+    #[test]
+    fn a_rejected_puzzle_is_counted_once() {
+        let puzzle = PuzzleExtractor::new()
+            .extract(Difficulty::Easy, include_str!("../../resource/sample.html"))
+            .unwrap();
+        let mut loaded = Loaded {
+            puzzles: vec![puzzle],
+            failures: Vec::new(),
+        };
+
+        loaded.retain(|_| Err(Error::Unsolvable));
+        assert!(loaded.puzzles.is_empty());
+        assert_eq!(loaded.failures.len(), 1);
+
+        // A lone failure is returned rather than summarized as "1 of 2 puzzles".
+        assert!(matches!(loaded.finish(), Err(Error::Unsolvable)));
+    }
+}
@@ -0,0 +1,44 @@
+use std::io;
+
+use clap::Clap;
+
+use websudoku::{verify::Verification, Error, Result};
+
+use super::{read_grid, source::Source};
+
+/// Check a filled-in grid against the puzzle's solution
+#[derive(Clap, Clone, Debug)]
+pub struct Verify {
+    /// The filled-in grid, in the layout written by the csv format. Use - to read from stdin.
+    #[clap(short, long)]
+    grid: String,
+
+    /// Print the result as a line of JSON
+    #[clap(long)]
+    json: bool,
+
+    #[clap(flatten)]
+    source: Source,
+}
+
+impl Verify {
+    pub fn run(&self) -> Result<()> {
+        let puzzle = self.source.load()?.single("verify")?;
+        let answer = read_grid(&self.grid)?;
+        let verification = Verification::new(&puzzle, &answer)?;
+
+        if self.json {
+            verification.write_json(&puzzle, io::stdout())?;
+        } else {
+            verification.write_text(&puzzle, io::stdout())?;
+        }
+
+        match verification.is_solved() {
+            true => Ok(()),
+            false => Err(Error::IncorrectAnswer {
+                wrong: verification.wrong.len(),
+                missing: verification.missing.len(),
+            }),
+        }
+    }
+}
@@ -0,0 +1,66 @@
+use std::{
+    fmt::{self, Display},
+    str::FromStr,
+};
+
+use serde::{Deserialize, Serialize};
+
+/// The difficulty level websudoku assigns to a puzzle.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub enum Difficulty {
+    Easy,
+    Medium,
+    Hard,
+    #[default]
+    Evil,
+}
+
+impl Difficulty {
+    /// The numeric level used in websudoku urls (`level=1` through `level=4`).
+    pub fn level(self) -> u8 {
+        match self {
+            Difficulty::Easy => 1,
+            Difficulty::Medium => 2,
+            Difficulty::Hard => 3,
+            Difficulty::Evil => 4,
+        }
+    }
+
+    /// Map a websudoku `level` value back to a difficulty.
+    ///
+    /// Unrecognized levels are treated as `Evil`, matching the site's own behavior.
+    pub fn from_level(level: &str) -> Self {
+        match level {
+            "1" => Difficulty::Easy,
+            "2" => Difficulty::Medium,
+            "3" => Difficulty::Hard,
+            _ => Difficulty::Evil,
+        }
+    }
+}
+
+impl Display for Difficulty {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Difficulty::Easy => f.write_str("Easy"),
+            Difficulty::Medium => f.write_str("Medium"),
+            Difficulty::Hard => f.write_str("Hard"),
+            Difficulty::Evil => f.write_str("Evil"),
+        }
+    }
+}
+
+impl FromStr for Difficulty {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_ref() {
+            "easy" => Ok(Difficulty::Easy),
+            "medium" => Ok(Difficulty::Medium),
+            "hard" => Ok(Difficulty::Hard),
+            "evil" => Ok(Difficulty::Evil),
+
+            _ => Err(format!("Unrecognized difficulty setting: {}", s)),
+        }
+    }
+}
@@ -0,0 +1,140 @@
+use std::{
+    fmt::{self, Display},
+    io,
+};
+
+use thiserror::Error;
+
+use crate::grid::{self, Unit};
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// A hidden input on a websudoku page carrying part of the puzzle.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Field {
+    /// `pid`: the puzzle identifier.
+    PuzzleId,
+    /// `cheat`: the completed grid.
+    Solution,
+    /// `editmask`: which cells are left blank.
+    Mask,
+}
+
+impl Field {
+    /// The id of the hidden input holding this field.
+    pub fn input_id(self) -> &'static str {
+        match self {
+            Field::PuzzleId => "pid",
+            Field::Solution => "cheat",
+            Field::Mask => "editmask",
+        }
+    }
+}
+
+impl Display for Field {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.input_id())
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("{0}")]
+    InvalidInput(String),
+
+    #[error("network error: {0}")]
+    Network(#[from] reqwest::Error),
+
+    #[error("server responded with {status} for {url}")]
+    Status {
+        status: reqwest::StatusCode,
+        url: String,
+    },
+
+    #[error("page has no `{0}` field")]
+    MissingField(Field),
+
+    #[error("`{field}` has malformed digit {value:?} at position {index}")]
+    MalformedDigit {
+        field: Field,
+        index: usize,
+        value: char,
+    },
+
+    #[error("`{field}` has {count} cells; expected 81")]
+    CellCount { field: Field, count: usize },
+
+    #[error("cell {index} holds {digit}; expected a digit from 1 to 9")]
+    DigitOutOfRange { index: usize, digit: u8 },
+
+    #[error("solution repeats {digit} in {unit}")]
+    Conflict { unit: Unit, digit: u8 },
+
+    #[error("the givens have no solution")]
+    Unsolvable,
+
+    #[error("the givens have at least {0} solutions")]
+    MultipleSolutions(usize),
+
+    #[error(
+        "solution holds {found} at {}, but solving the givens gives {expected}",
+        grid::cell_name(*index)
+    )]
+    SolutionMismatch {
+        index: usize,
+        expected: u8,
+        found: u8,
+    },
+
+    #[error("answer has {wrong} wrong and {missing} missing cells")]
+    IncorrectAnswer { wrong: usize, missing: usize },
+
+    #[error(transparent)]
+    Io(#[from] io::Error),
+}
+
+impl Error {
+    /// Whether retrying the request that caused this error might succeed.
+    ///
+    /// Connection failures, timeouts, `429 Too Many Requests`, server errors, and pages
+    /// without a solution (which is what websudoku serves when throttling) are transient.
+    pub fn is_transient(&self) -> bool {
+        match self {
+            Error::Network(e) => e.is_timeout() || e.is_connect() || e.is_request() || e.is_body(),
+            Error::Status { status, .. } => {
+                *status == reqwest::StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+            }
+            Error::MissingField(field) => *field == Field::Solution,
+            _ => false,
+        }
+    }
+
+    /// The process exit code reported for this category of error.
+    ///
+    /// | code | category            |
+    /// |------|---------------------|
+    /// | 1    | invalid input       |
+    /// | 2    | io                  |
+    /// | 3    | network             |
+    /// | 4    | http status         |
+    /// | 5    | missing field       |
+    /// | 6    | malformed digit     |
+    /// | 7    | wrong cell count    |
+    /// | 8    | invalid solution    |
+    /// | 9    | failed cross-check  |
+    /// | 10   | incorrect answer    |
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Error::InvalidInput(_) => 1,
+            Error::Io(_) => 2,
+            Error::Network(_) => 3,
+            Error::Status { .. } => 4,
+            Error::MissingField(_) => 5,
+            Error::MalformedDigit { .. } => 6,
+            Error::CellCount { .. } => 7,
+            Error::DigitOutOfRange { .. } | Error::Conflict { .. } => 8,
+            Error::Unsolvable | Error::MultipleSolutions(_) | Error::SolutionMismatch { .. } => 9,
+            Error::IncorrectAnswer { .. } => 10,
+        }
+    }
+}
@@ -0,0 +1,176 @@
+use std::collections::HashMap;
+
+use regex::{Regex, RegexBuilder};
+
+use crate::{
+    error::{Error, Field, Result},
+    puzzle, Difficulty, Puzzle,
+};
+
+/// Extracts puzzle data from the hidden inputs of a websudoku page.
+pub struct PuzzleExtractor {
+    pattern: Regex,
+    level_pattern: Regex,
+}
+
+impl PuzzleExtractor {
+    pub fn new() -> Self {
+        Self {
+            pattern: input_regex(),
+            level_pattern: level_regex(),
+        }
+    }
+
+    /// The difficulty recorded in the page's hidden `level` input, if there is one.
+    ///
+    /// This is useful for pages saved from a browser, where the url is no longer available.
+    pub fn difficulty(&self, content: &str) -> Option<Difficulty> {
+        self.level_pattern
+            .captures(content)
+            .map(|captures| Difficulty::from_level(&captures[1]))
+    }
+
+    /// Extract a puzzle from the html content of a websudoku page.
+    ///
+    /// The page itself does not reliably state its difficulty, so the caller supplies it.
+    pub fn extract(&self, difficulty: Difficulty, content: &str) -> Result<Puzzle> {
+        let map = self.build_extraction_map(content);
+        let field = |field: Field| {
+            map.get(field.input_id())
+                .copied()
+                .ok_or(Error::MissingField(field))
+        };
+
+        let id = field(Field::PuzzleId)?;
+        puzzle::check_id(id)?;
+
+        Ok(Puzzle {
+            difficulty,
+            id: id.to_string(),
+            solution: parse_cells(Field::Solution, field(Field::Solution)?)?,
+            mask: parse_cells(Field::Mask, field(Field::Mask)?)?
+                .into_iter()
+                .map(|u| u == 1)
+                .collect(),
+        })
+    }
+
+    fn build_extraction_map<'a>(&self, content: &'a str) -> HashMap<&'a str, &'a str> {
+        self.pattern
+            .captures_iter(content)
+            .map(|x| (x.get(1).unwrap().as_str(), x.get(2).unwrap().as_str()))
+            .collect()
+    }
+}
+
+impl Default for PuzzleExtractor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn parse_cells(field: Field, value: &str) -> Result<Vec<u8>> {
+    let cells = value
+        .chars()
+        .enumerate()
+        .map(|(index, value)| match value.to_digit(10) {
+            Some(digit) => Ok(digit as u8),
+            None => Err(Error::MalformedDigit {
+                field,
+                index,
+                value,
+            }),
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    match cells.len() {
+        81 => Ok(cells),
+        count => Err(Error::CellCount { field, count }),
+    }
+}
+
+fn input_regex() -> Regex {
+    RegexBuilder::new(r#"<input.+?id="(\S+)".+?value="([^"]*)""#)
+        .case_insensitive(true)
+        .dot_matches_new_line(true)
+        .build()
+        .unwrap()
+}
+
+fn level_regex() -> Regex {
+    RegexBuilder::new(r#"<input[^>]+?name="?level"?[^>]+?value="(\d)""#)
+        .case_insensitive(true)
+        .build()
+        .unwrap()
+}
+
+#[cfg(test)]
+mod test {
+    use super::PuzzleExtractor;
+    use crate::{Difficulty, Error, Field, Puzzle};
+
+    #[test]
+    fn input_regex_works() {
+        let content = include_str!("../resource/sample.html");
+        let extractor = PuzzleExtractor::new();
+
+        let actual = extractor.extract(Difficulty::Evil, content).unwrap();
+        let expected = Puzzle {
+            difficulty: Difficulty::Evil,
+            id: String::from("7042100266"),
+            solution: vec![
+                9, 8, 4, 2, 7, 3, 6, 5, 1, 7, 1, 5, 6, 8, 4, 9, 2, 3, 3, 2, 6, 9, 5, 1, 7, 4, 8, 8,
+                4, 9, 7, 3, 2, 1, 6, 5, 6, 3, 7, 8, 1, 5, 2, 9, 4, 2, 5, 1, 4, 6, 9, 3, 8, 7, 1, 9,
+                3, 5, 4, 6, 8, 7, 2, 5, 7, 2, 3, 9, 8, 4, 1, 6, 4, 6, 8, 1, 2, 7, 5, 3, 9,
+            ],
+            mask: vec![
+                true, true, true, true, false, true, false, true, true, true, false, false, false,
+                true, true, false, true, true, false, true, true, false, true, true, false, true,
+                true, true, false, false, true, true, false, true, false, false, false, true,
+                false, false, false, false, false, true, false, false, false, true, false, true,
+                true, false, false, true, true, true, false, true, true, false, true, true, false,
+                true, true, false, true, true, false, false, false, true, true, true, false, true,
+                false, true, true, true, true,
+            ],
+        };
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn page_difficulty() {
+        let content = include_str!("../resource/sample.html");
+        let extractor = PuzzleExtractor::new();
+
+        assert_eq!(extractor.difficulty(content), Some(Difficulty::Easy));
+        assert_eq!(extractor.difficulty("<html></html>"), None);
+    }
+
+    #[test]
+    fn missing_fields_are_named() {
+        let content =
+            include_str!("../resource/sample.html").replace(r#"ID="cheat""#, r#"ID="other""#);
+        let extractor = PuzzleExtractor::new();
+
+        match extractor.extract(Difficulty::Evil, &content) {
+            Err(Error::MissingField(Field::Solution)) => {}
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn rejects_non_numeric_ids() {
+        let content = include_str!("../resource/sample.html")
+            .replace(r#"VALUE="7042100266""#, r#"VALUE="sub/..evil""#);
+        let extractor = PuzzleExtractor::new();
+
+        match extractor.extract(Difficulty::Evil, &content) {
+            Err(Error::MalformedDigit {
+                field: Field::PuzzleId,
+                index: 0,
+                value: 's',
+            }) => {}
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+}
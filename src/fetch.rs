@@ -0,0 +1,63 @@
+use reqwest::blocking::Client;
+
+use crate::{
+    error::{Error, Result},
+    Puzzle, PuzzleExtractor, PuzzleRef,
+};
+
+static USER_AGENT: &str =
+    "Mozilla/5.0 (Windows NT 10.0; Win64; x64; rv:83.0) Gecko/20100101 Firefox/83.0";
+
+/// Downloads websudoku pages.
+///
+/// A single `Fetcher` should be reused for any number of puzzles so that connections are
+/// shared between requests.
+pub struct Fetcher {
+    client: Client,
+}
+
+impl Fetcher {
+    pub fn new() -> Result<Self> {
+        Ok(Self {
+            client: Client::builder().user_agent(USER_AGENT).build()?,
+        })
+    }
+
+    /// Download the html content of the page hosting a puzzle.
+    ///
+    /// Responses with a non-success status are reported as `Error::Status`.
+    pub fn fetch(&self, puzzle: &PuzzleRef) -> Result<String> {
+        let url = puzzle.url();
+        let response = self.client.get(&url).send()?;
+        let status = response.status();
+
+        if !status.is_success() {
+            return Err(Error::Status { status, url });
+        }
+
+        Ok(response.text()?)
+    }
+}
+
+/// Downloads and extracts puzzles, sharing one client and extractor between them.
+pub struct Downloader {
+    fetcher: Fetcher,
+    extractor: PuzzleExtractor,
+}
+
+impl Downloader {
+    pub fn new() -> Result<Self> {
+        Ok(Self {
+            fetcher: Fetcher::new()?,
+            extractor: PuzzleExtractor::new(),
+        })
+    }
+
+    /// Download a puzzle, rejecting any puzzle which fails [`Puzzle::validate`].
+    pub fn download(&self, puzzle: &PuzzleRef) -> Result<Puzzle> {
+        let content = self.fetcher.fetch(puzzle)?;
+        let puzzle = self.extractor.extract(puzzle.difficulty, &content)?;
+        puzzle.validate()?;
+        Ok(puzzle)
+    }
+}
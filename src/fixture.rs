@@ -0,0 +1,15 @@
+//! The puzzle shared by unit tests: the one saved in `resource/sample.html`.
+
+use crate::{Difficulty, Puzzle, PuzzleExtractor};
+
+/// Easy 7042100266, a legal puzzle with 35 givens.
+pub fn puzzle() -> Puzzle {
+    PuzzleExtractor::new()
+        .extract(Difficulty::Easy, include_str!("../resource/sample.html"))
+        .unwrap()
+}
+
+/// The completed grid of [`puzzle`].
+pub fn solution() -> Vec<u8> {
+    puzzle().solution
+}
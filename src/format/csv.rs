@@ -0,0 +1,218 @@
+use std::io::{self, Write};
+
+use ::csv::{Terminator, WriterBuilder};
+
+use crate::{
+    error::{Error, Result},
+    grid, Puzzle,
+};
+
+use super::Options;
+
+/// The header row naming the columns.
+const HEADER: [&str; 9] = ["c1", "c2", "c3", "c4", "c5", "c6", "c7", "c8", "c9"];
+
+/// Write a puzzle as nine rows of exactly nine fields, optionally after a header row.
+///
+/// Blank cells are written as `options.blank`. With `options.with_solution` every cell holds
+/// its solution digit instead, and givens are marked by brackets, e.g. `[5]`. A puzzle
+/// whose solution or mask does not have 81 cells fails before anything is written.
+pub(crate) fn write(puzzle: &Puzzle, options: &Options, w: impl Write) -> io::Result<()> {
+    if puzzle.solution.len() != grid::CELLS || puzzle.mask.len() != grid::CELLS {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+                "puzzle has {} solution and {} mask cells; expected 81",
+                puzzle.solution.len(),
+                puzzle.mask.len()
+            ),
+        ));
+    }
+
+    let mut writer = WriterBuilder::new()
+        .terminator(Terminator::Any(b'\n'))
+        .from_writer(w);
+
+    if options.header {
+        writer.write_record(HEADER)?;
+    }
+
+    for (row, mask) in puzzle.solution.chunks(9).zip(puzzle.mask.chunks(9)) {
+        writer.write_record(row.iter().zip(mask).map(|(&value, &can_edit)| {
+            match (can_edit, options.with_solution) {
+                (true, false) => options.blank.clone(),
+                (false, true) => format!("[{}]", value),
+                _ => value.to_string(),
+            }
+        }))?;
+    }
+
+    writer.flush()
+}
+
+/// Read every grid from csv text, with `0` for blank cells.
+///
+/// Both the layout written by [`write`] and the older layout of
+/// [`Puzzle::write_masked_puzzle`] are accepted. The latter followed every filled cell with a
+/// comma, so a row ending in a filled cell has a tenth, empty field. Any field without a
+/// digit from 1 to 9 is a blank, so placeholders such as `.` or `0` read back; fields may be
+/// quoted, and header rows and blank lines between grids are skipped. In a grid with
+/// bracketed givens, as written with the solution, unbracketed digits are read as blanks.
+pub fn read_grids(content: &str) -> Result<Vec<Vec<u8>>> {
+    // Each cell's digit, and whether it was marked as a given.
+    let mut cells = Vec::new();
+
+    for (idx, line) in content.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let line_error =
+            |message: String| Error::InvalidInput(format!("line {}: {}", idx + 1, message));
+
+        let mut fields: Vec<&str> = line
+            .split(',')
+            .map(|field| field.trim().trim_matches('"').trim())
+            .collect();
+        if fields.len() == 10 && fields[9].is_empty() {
+            fields.pop();
+        }
+        if fields.len() != 9 {
+            return Err(line_error(format!("{} cells; expected 9", fields.len())));
+        }
+        if fields
+            .iter()
+            .zip(&HEADER)
+            .all(|(field, name)| field.eq_ignore_ascii_case(name))
+        {
+            continue;
+        }
+
+        for field in fields {
+            let (field, marked) = match field.strip_prefix('[').and_then(|f| f.strip_suffix(']')) {
+                Some(field) => (field, true),
+                None => (field, false),
+            };
+
+            let digit = match field.parse() {
+                Ok(digit @ 1..=9) => digit,
+                _ if !marked && !field.bytes().any(|u| (b'1'..=b'9').contains(&u)) => 0,
+                _ => {
+                    return Err(line_error(format!(
+                        "{:?} is not a digit from 1 to 9",
+                        field
+                    )))
+                }
+            };
+            cells.push((digit, marked));
+        }
+    }
+
+    if cells.is_empty() || cells.len() % grid::CELLS != 0 {
+        return Err(Error::InvalidInput(format!(
+            "{} rows; expected a multiple of 9",
+            cells.len() / 9
+        )));
+    }
+
+    Ok(cells
+        .chunks(grid::CELLS)
+        .map(|grid| {
+            let marked = grid.iter().any(|&(_, marked)| marked);
+            grid.iter()
+                .map(|&(digit, given)| if marked && !given { 0 } else { digit })
+                .collect()
+        })
+        .collect())
+}
+
+/// Read a single grid from csv text; see [`read_grids`].
+pub fn read_grid(content: &str) -> Result<Vec<u8>> {
+    let mut grids = read_grids(content)?;
+    match grids.len() {
+        1 => Ok(grids.remove(0)),
+        count => Err(Error::InvalidInput(format!(
+            "{} grids; expected one",
+            count
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{read_grid, read_grids, write};
+    use crate::{fixture::puzzle, format::Options};
+
+    #[test]
+    fn reads_old_masked_layout() {
+        // Filled cells were followed by a comma, so rows ending in one had ten fields.
+        let csv = ",,,,7,,6,,\n,1,5,6,,,9,,\n3,,,9,,,7,,\n,4,9,,,2,,6,5,\n6,,7,8,1,5,2,,4,\n\
+                   2,5,,4,,,3,8,\n,,3,,,6,,,2,\n,,2,,,8,4,1,\n,,8,,2,,,,\n";
+
+        assert_eq!(read_grid(csv).unwrap(), puzzle().givens());
+    }
+
+    #[test]
+    fn writes_nine_fields_per_row() {
+        let puzzle = puzzle();
+        let options = Options {
+            header: true,
+            blank: String::from("."),
+            ..Options::default()
+        };
+
+        let mut csv = Vec::new();
+        write(&puzzle, &options, &mut csv).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 10);
+        assert_eq!(lines[0], "c1,c2,c3,c4,c5,c6,c7,c8,c9");
+        assert_eq!(lines[1], ".,.,.,.,7,.,6,.,.");
+        assert!(lines.iter().all(|line| line.split(',').count() == 9));
+        assert_eq!(read_grid(&csv).unwrap(), puzzle.givens());
+    }
+
+    #[test]
+    fn solution_marks_givens() {
+        let puzzle = puzzle();
+        let options = Options {
+            with_solution: true,
+            ..Options::default()
+        };
+
+        let mut csv = Vec::new();
+        write(&puzzle, &options, &mut csv).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+
+        assert!(csv.starts_with("9,8,4,2,[7],3,[6],5,1\n"));
+        assert_eq!(read_grid(&csv).unwrap(), puzzle.givens());
+    }
+
+    #[test]
+    fn rejects_short_puzzles() {
+        let mut puzzle = puzzle();
+        puzzle.solution.truncate(72);
+
+        let mut csv = Vec::new();
+        assert!(write(&puzzle, &Options::default(), &mut csv).is_err());
+        assert!(csv.is_empty());
+    }
+
+    #[test]
+    fn reads_plain_csv() {
+        let row = "\"9\", 8 ,.,0,,,,,1\r\n";
+        let grids = read_grids(&format!("{}\n{}", row.repeat(9), row.repeat(9))).unwrap();
+
+        assert_eq!(grids.len(), 2);
+        assert_eq!(grids[1][..9], [9, 8, 0, 0, 0, 0, 0, 0, 1]);
+    }
+
+    #[test]
+    fn rejects_bad_rows() {
+        assert!(read_grid(&"1,2,3\n".repeat(9)).is_err());
+        assert!(read_grid(&",,,,x5,,,,\n".repeat(9)).is_err());
+        assert!(read_grid(&",,,,[0],,,,\n".repeat(9)).is_err());
+        assert!(read_grid(&",,,,,,,,\n".repeat(8)).is_err());
+    }
+}
@@ -0,0 +1,52 @@
+use std::io::{self, Write};
+
+use crate::Puzzle;
+
+use super::json::Document;
+
+/// The page, with `{{title}}` and `{{puzzle}}` standing in for the puzzle's name and data.
+static TEMPLATE: &str = include_str!("../../resource/page.html");
+
+/// Write a self-contained page for playing the puzzle in a browser, without a connection.
+///
+/// The puzzle is embedded as its JSON document, solution included, so that the page can
+/// check answers itself.
+pub(super) fn write(puzzle: &Puzzle, mut w: impl Write) -> io::Result<()> {
+    // A script element ends at the first "</", wherever it appears.
+    let data = serde_json::to_string(&Document::new(puzzle))?.replace("</", r"<\/");
+    let page = TEMPLATE
+        .replace("{{title}}", &escape(&puzzle.puzzle_ref().to_string()))
+        .replace("{{puzzle}}", &data);
+    w.write_all(page.as_bytes())
+}
+
+/// Escape text for use in an element.
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod test {
+    use super::write;
+    use crate::{fixture, Puzzle};
+
+    #[test]
+    fn embeds_the_puzzle() {
+        let puzzle = Puzzle {
+            id: String::from("</script>"),
+            ..fixture::puzzle()
+        };
+
+        let mut html = Vec::new();
+        write(&puzzle, &mut html).unwrap();
+        let html = String::from_utf8(html).unwrap();
+
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(!html.contains("{{"));
+        assert!(html.contains("<title>Easy &lt;/script&gt;</title>"));
+        assert_eq!(html.matches("</script>").count(), 2);
+        assert!(html.contains(r#""solution":[[9,8,4,2,7,3,6,5,1],"#));
+    }
+}
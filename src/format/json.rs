@@ -0,0 +1,56 @@
+use serde::Serialize;
+
+use crate::{Difficulty, Puzzle};
+
+/// The JSON representation of a puzzle.
+///
+/// Grids are written as nine rows of nine cells; blank cells in `givens` are `0`.
+#[derive(Serialize)]
+pub(super) struct Document<'a> {
+    id: &'a str,
+    difficulty: Difficulty,
+    url: String,
+    givens: Vec<Vec<u8>>,
+    solution: Vec<&'a [u8]>,
+    mask: Vec<&'a [bool]>,
+}
+
+impl<'a> Document<'a> {
+    pub(super) fn new(puzzle: &'a Puzzle) -> Self {
+        Self {
+            id: &puzzle.id,
+            difficulty: puzzle.difficulty,
+            url: puzzle.puzzle_ref().url(),
+            givens: puzzle.givens().chunks(9).map(<[u8]>::to_vec).collect(),
+            solution: puzzle.solution.chunks(9).collect(),
+            mask: puzzle.mask.chunks(9).collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::Value;
+
+    use super::Document;
+    use crate::fixture;
+
+    #[test]
+    fn document_shape() {
+        let puzzle = fixture::puzzle();
+
+        let value = serde_json::to_value(Document::new(&puzzle)).unwrap();
+
+        assert_eq!(value["difficulty"], "Easy");
+        assert_eq!(
+            value["url"],
+            "https://grid.websudoku.com/?level=1&set_id=7042100266"
+        );
+        assert_eq!(
+            value["givens"][0],
+            Value::from(vec![0, 0, 0, 0, 7, 0, 6, 0, 0])
+        );
+        assert_eq!(value["solution"].as_array().unwrap().len(), 9);
+        assert_eq!(value["mask"][8][8], true);
+    }
+}
@@ -0,0 +1,146 @@
+use std::io::{self, Write};
+
+use crate::Puzzle;
+
+const PREAMBLE: &str = r"\documentclass{article}
+\usepackage{sudoku}
+\begin{document}";
+
+/// Write puzzles as `sudoku` environments for the LaTeX package of the same name.
+///
+/// Each grid is preceded by a comment naming the puzzle. With `with_solution`, the solutions
+/// follow the puzzles in the same order. With `standalone`, the grids are wrapped in a
+/// complete document, under headings rather than comments, with the solutions on a page of
+/// their own.
+pub(super) fn write(
+    puzzles: &[Puzzle],
+    with_solution: bool,
+    standalone: bool,
+    mut w: impl Write,
+) -> io::Result<()> {
+    if standalone {
+        writeln!(w, "{}", PREAMBLE)?;
+    }
+
+    for (idx, puzzle) in puzzles.iter().enumerate() {
+        if idx > 0 || standalone {
+            writeln!(w)?;
+        }
+        title(&puzzle.puzzle_ref().to_string(), standalone, &mut w)?;
+        block(&puzzle.givens(), &mut w)?;
+    }
+
+    if with_solution {
+        if standalone {
+            writeln!(w)?;
+            writeln!(w, r"\clearpage")?;
+            writeln!(w, r"\section*{{Solutions}}")?;
+        }
+        for puzzle in puzzles {
+            writeln!(w)?;
+            title(
+                &format!("Solution: {}", puzzle.puzzle_ref()),
+                standalone,
+                &mut w,
+            )?;
+            block(&puzzle.solution, &mut w)?;
+        }
+    }
+
+    if standalone {
+        writeln!(w)?;
+        writeln!(w, r"\end{{document}}")?;
+    }
+    Ok(())
+}
+
+/// Name a grid: with a heading in a standalone document, or a comment in a fragment.
+fn title(title: &str, standalone: bool, mut w: impl Write) -> io::Result<()> {
+    if standalone {
+        writeln!(w, r"\subsection*{{{}}}", escape(title))
+    } else {
+        writeln!(w, "% {}", title.replace('\n', " "))
+    }
+}
+
+/// Write a grid of values, with blanks for zeroes, as a `sudoku` environment.
+fn block(values: &[u8], mut w: impl Write) -> io::Result<()> {
+    writeln!(w, r"\begin{{sudoku}}")?;
+    for row in values.chunks(9) {
+        let mut line = String::from("|");
+        for &value in row {
+            line.push(match value {
+                0 => ' ',
+                value => char::from(b'0' + value),
+            });
+            line.push('|');
+        }
+        writeln!(w, "{}.", line)?;
+    }
+    writeln!(w, r"\end{{sudoku}}")
+}
+
+/// Escape the characters LaTeX treats specially in running text.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str(r"\textbackslash{}"),
+            '~' => escaped.push_str(r"\textasciitilde{}"),
+            '^' => escaped.push_str(r"\textasciicircum{}"),
+            '&' | '%' | '$' | '#' | '_' | '{' | '}' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod test {
+    use super::{escape, write};
+    use crate::{fixture, Puzzle};
+
+    fn puzzle(id: &str) -> Puzzle {
+        Puzzle {
+            id: String::from(id),
+            ..fixture::puzzle()
+        }
+    }
+
+    #[test]
+    fn sudoku_block() {
+        let mut tex = Vec::new();
+        write(&[puzzle("1")], false, false, &mut tex).unwrap();
+        let tex = String::from_utf8(tex).unwrap();
+        let lines: Vec<&str> = tex.lines().collect();
+
+        assert_eq!(lines.len(), 12);
+        assert_eq!(lines[0], "% Easy 1");
+        assert_eq!(lines[1], r"\begin{sudoku}");
+        assert_eq!(lines[2], "| | | | |7| |6| | |.");
+        assert_eq!(lines[3], "| |1|5|6| | |9| | |.");
+        assert_eq!(lines[11], r"\end{sudoku}");
+    }
+
+    #[test]
+    fn standalone_document_with_solutions() {
+        let mut tex = Vec::new();
+        write(&[puzzle("a_1"), puzzle("2")], true, true, &mut tex).unwrap();
+        let tex = String::from_utf8(tex).unwrap();
+
+        assert!(tex.starts_with("\\documentclass{article}\n\\usepackage{sudoku}\n"));
+        assert!(tex.contains(r"\subsection*{Easy a\_1}"));
+        assert!(tex.contains(r"\subsection*{Solution: Easy 2}"));
+        assert_eq!(tex.matches(r"\begin{sudoku}").count(), 4);
+        assert_eq!(tex.matches("|9|8|4|2|7|3|6|5|1|.").count(), 2);
+        assert!(tex.trim_end().ends_with(r"\end{document}"));
+    }
+
+    #[test]
+    fn escapes_special_characters() {
+        assert_eq!(escape(r"50% & #1_\"), r"50\% \& \#1\_\textbackslash{}");
+    }
+}
@@ -0,0 +1,49 @@
+use std::io::{self, Write};
+
+use crate::Puzzle;
+
+/// Write the givens as a single line of 81 characters, with `.` for blank cells.
+///
+/// With `with_solution`, the line is followed by `:` and the 81 digits of the solution.
+pub(super) fn write(puzzle: &Puzzle, with_solution: bool, mut w: impl Write) -> io::Result<()> {
+    let givens: String = puzzle
+        .givens()
+        .into_iter()
+        .map(|value| match value {
+            0 => '.',
+            value => char::from(b'0' + value),
+        })
+        .collect();
+    w.write_all(givens.as_bytes())?;
+
+    if with_solution {
+        let solution: String = puzzle
+            .solution
+            .iter()
+            .map(|&value| char::from(b'0' + value))
+            .collect();
+        write!(w, ":{}", solution)?;
+    }
+
+    writeln!(w)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::fixture;
+
+    #[test]
+    fn write_line() {
+        let puzzle = fixture::puzzle();
+
+        let mut buf = Vec::new();
+        super::write(&puzzle, true, &mut buf).unwrap();
+        let line = String::from_utf8(buf).unwrap();
+        let (givens, solution) = line.trim_end().split_at(81);
+
+        assert_eq!(&givens[..9], "....7.6..");
+        assert_eq!(givens.len(), 81);
+        assert_eq!(&solution[..4], ":984");
+        assert_eq!(solution.len(), 82);
+    }
+}
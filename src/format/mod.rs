@@ -0,0 +1,217 @@
+//! Writers for the output formats supported by the command line tool, and a reader for
+//! csv grids so that written puzzles can be used as inputs.
+
+use std::{
+    fmt::{self, Display},
+    io::{self, Write},
+    str::FromStr,
+};
+
+use crate::{render::Layout, Puzzle};
+
+pub mod csv;
+mod html;
+mod json;
+mod latex;
+mod line;
+mod pdf;
+mod png;
+mod svg;
+mod text;
+
+/// Settings shared by the output formats.
+#[derive(Clone, Debug)]
+pub struct Options {
+    /// Include the solution alongside the givens, for formats which support it.
+    pub with_solution: bool,
+    /// Write a header row naming the columns, for formats which support it.
+    pub header: bool,
+    /// Wrap LaTeX output in a complete document rather than writing bare environments.
+    pub standalone: bool,
+    /// The placeholder written for blank cells in csv output; empty by default.
+    pub blank: String,
+    /// Distinguish givens from blanks with ANSI colors in text output.
+    pub color: bool,
+    /// Draw givens in bold when drawing the solution, for graphical formats.
+    pub bold_givens: bool,
+    /// The size and fonts of graphical formats.
+    pub layout: Layout,
+    /// The number of puzzles on each page of a pdf booklet: 1, 2, 4 or 6.
+    pub per_page: usize,
+    /// The resolution of png images; the layout's units are pixels at 96 dpi.
+    pub dpi: f32,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            with_solution: false,
+            header: false,
+            standalone: false,
+            blank: String::new(),
+            color: false,
+            bold_givens: false,
+            layout: Layout::default(),
+            per_page: 1,
+            dpi: 96.0,
+        }
+    }
+}
+
+/// An output format for puzzles.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum Format {
+    /// The givens as nine rows of nine comma separated fields.
+    #[default]
+    Csv,
+    /// A self-contained web page for playing the puzzle, with the solution embedded.
+    Html,
+    /// A JSON object with the givens, solution, mask and source url.
+    Json,
+    /// Environments for the LaTeX `sudoku` package, optionally in a standalone document.
+    Latex,
+    /// The givens as one line of 81 characters, optionally followed by `:` and the solution.
+    Line,
+    /// A booklet of puzzles with an answer key at the back.
+    Pdf,
+    /// A png image of the grid.
+    Png,
+    /// A standalone svg image of the grid.
+    Svg,
+    /// The grid drawn with box-drawing characters, for reading in a terminal.
+    Text,
+}
+
+impl Format {
+    /// The file extension used for this format.
+    pub fn extension(self) -> &'static str {
+        match self {
+            Format::Csv => "csv",
+            Format::Html => "html",
+            Format::Json => "json",
+            Format::Latex => "tex",
+            Format::Line | Format::Text => "txt",
+            Format::Pdf => "pdf",
+            Format::Png => "png",
+            Format::Svg => "svg",
+        }
+    }
+
+    /// Write a single puzzle as a complete document.
+    pub fn write(self, puzzle: &Puzzle, options: &Options, mut w: impl Write) -> io::Result<()> {
+        match self {
+            Format::Csv => csv::write(puzzle, options, w),
+            Format::Html => html::write(puzzle, w),
+            Format::Json => {
+                serde_json::to_writer_pretty(&mut w, &json::Document::new(puzzle))?;
+                writeln!(w)
+            }
+            Format::Latex => latex::write(
+                std::slice::from_ref(puzzle),
+                options.with_solution,
+                options.standalone,
+                w,
+            ),
+            Format::Line => line::write(puzzle, options.with_solution, w),
+            Format::Pdf => pdf::write(std::slice::from_ref(puzzle), options, w),
+            Format::Png => png::write(puzzle, options, w),
+            Format::Svg => svg::write(puzzle, options, w),
+            Format::Text => text::write(puzzle, options.with_solution, options.color, w),
+        }
+    }
+
+    /// Write several puzzles to one stream.
+    ///
+    /// JSON is written as JSON Lines, one compact object per puzzle, and the line format
+    /// writes one puzzle per line; csv and text grids are separated by a blank line. A web
+    /// page, svg or png image holds a single puzzle, so several puzzles cannot share a
+    /// stream; a pdf booklet or standalone LaTeX document holds them all.
+    pub fn write_many(
+        self,
+        puzzles: &[Puzzle],
+        options: &Options,
+        mut w: impl Write,
+    ) -> io::Result<()> {
+        match self {
+            Format::Csv => {
+                for (idx, puzzle) in puzzles.iter().enumerate() {
+                    if idx > 0 {
+                        writeln!(w)?;
+                    }
+                    csv::write(puzzle, options, &mut w)?;
+                }
+                Ok(())
+            }
+            Format::Json => {
+                for puzzle in puzzles {
+                    serde_json::to_writer(&mut w, &json::Document::new(puzzle))?;
+                    writeln!(w)?;
+                }
+                Ok(())
+            }
+            Format::Line => {
+                for puzzle in puzzles {
+                    line::write(puzzle, options.with_solution, &mut w)?;
+                }
+                Ok(())
+            }
+            Format::Latex => latex::write(puzzles, options.with_solution, options.standalone, w),
+            Format::Pdf => pdf::write(puzzles, options, w),
+            Format::Html | Format::Png | Format::Svg => match puzzles {
+                [puzzle] => self.write(puzzle, options, w),
+                _ => Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!(
+                        "{} files hold one puzzle each; write several to a directory instead",
+                        self
+                    ),
+                )),
+            },
+            Format::Text => {
+                for (idx, puzzle) in puzzles.iter().enumerate() {
+                    if idx > 0 {
+                        writeln!(w)?;
+                    }
+                    text::write(puzzle, options.with_solution, options.color, &mut w)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Format::Csv => "csv",
+            Format::Html => "html",
+            Format::Json => "json",
+            Format::Latex => "latex",
+            Format::Line => "line",
+            Format::Pdf => "pdf",
+            Format::Png => "png",
+            Format::Svg => "svg",
+            Format::Text => "text",
+        })
+    }
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_ref() {
+            "csv" => Ok(Format::Csv),
+            "html" => Ok(Format::Html),
+            "json" => Ok(Format::Json),
+            "latex" | "tex" => Ok(Format::Latex),
+            "line" => Ok(Format::Line),
+            "pdf" => Ok(Format::Pdf),
+            "png" => Ok(Format::Png),
+            "svg" => Ok(Format::Svg),
+            "text" => Ok(Format::Text),
+
+            _ => Err(format!("Unrecognized format: {}", s)),
+        }
+    }
+}
@@ -0,0 +1,222 @@
+use std::io::{self, Write};
+
+use pdf_writer::{Content, Name, Pdf, Rect, Ref, Str};
+
+use crate::{
+    render::{self, Layout},
+    Puzzle,
+};
+
+use super::Options;
+
+/// The width and height of an A4 page, in points.
+const PAGE_SIZE: (f32, f32) = (595.0, 842.0);
+const PAGE_MARGIN: f32 = 36.0;
+const TITLE_SIZE: f32 = 12.0;
+/// The space between the top of a puzzle's slot and its grid, holding the title.
+const TITLE_SPACE: f32 = 20.0;
+/// The least space between neighbouring grids.
+const SLOT_GAP: f32 = 18.0;
+const ANSWERS_PER_PAGE: usize = 6;
+
+/// The width of a digit in Helvetica and Helvetica Bold, as a fraction of the font size.
+const DIGIT_WIDTH: f32 = 0.556;
+
+const REGULAR: Name = Name(b"F1");
+const BOLD: Name = Name(b"F2");
+
+/// Write a booklet of puzzles, `options.per_page` to a page, followed by an answer key.
+///
+/// Each puzzle is titled with its number, difficulty and identifier, and its answer with
+/// the same; givens are bold in the answer key. Grid lines are scaled from
+/// `options.layout` to fit the page, and digits are set in Helvetica.
+pub(super) fn write(puzzles: &[Puzzle], options: &Options, mut w: impl Write) -> io::Result<()> {
+    let (columns, rows) = match options.per_page {
+        1 => (1, 1),
+        2 => (1, 2),
+        4 => (2, 2),
+        6 => (2, 3),
+        per_page => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "cannot fit {} puzzles on a page; use 1, 2, 4 or 6",
+                    per_page
+                ),
+            ))
+        }
+    };
+
+    let mut booklet = Booklet::new();
+    for (page, chunk) in puzzles.chunks(options.per_page).enumerate() {
+        let slots = chunk.iter().enumerate().map(|(idx, puzzle)| {
+            let number = page * options.per_page + idx + 1;
+            let title = format!("{}. {}", number, puzzle.puzzle_ref());
+            (title, render::glyphs(puzzle, false, false))
+        });
+        booklet.add_page(&options.layout, (columns, rows), slots);
+    }
+    for (page, chunk) in puzzles.chunks(ANSWERS_PER_PAGE).enumerate() {
+        let slots = chunk.iter().enumerate().map(|(idx, puzzle)| {
+            let number = page * ANSWERS_PER_PAGE + idx + 1;
+            let title = format!("Answer {}. {}", number, puzzle.puzzle_ref());
+            (title, render::glyphs(puzzle, true, true))
+        });
+        booklet.add_page(&options.layout, (2, 3), slots);
+    }
+
+    w.write_all(&booklet.finish())
+}
+
+struct Booklet {
+    pdf: Pdf,
+    pages: Vec<Ref>,
+    next_id: i32,
+}
+
+impl Booklet {
+    const CATALOG: Ref = Ref::new(1);
+    const PAGE_TREE: Ref = Ref::new(2);
+    const FONT: Ref = Ref::new(3);
+    const BOLD_FONT: Ref = Ref::new(4);
+
+    fn new() -> Self {
+        let mut pdf = Pdf::new();
+        pdf.catalog(Self::CATALOG).pages(Self::PAGE_TREE);
+        pdf.type1_font(Self::FONT)
+            .base_font(Name(b"Helvetica"))
+            .encoding_predefined(Name(b"WinAnsiEncoding"));
+        pdf.type1_font(Self::BOLD_FONT)
+            .base_font(Name(b"Helvetica-Bold"))
+            .encoding_predefined(Name(b"WinAnsiEncoding"));
+
+        Self {
+            pdf,
+            pages: Vec::new(),
+            next_id: 5,
+        }
+    }
+
+    fn next_ref(&mut self) -> Ref {
+        let id = Ref::new(self.next_id);
+        self.next_id += 1;
+        id
+    }
+
+    /// Add a page holding a grid of `(columns, rows)` slots, filled row by row.
+    fn add_page(
+        &mut self,
+        layout: &Layout,
+        (columns, rows): (usize, usize),
+        slots: impl Iterator<Item = (String, Vec<render::Glyph>)>,
+    ) {
+        let slot_width = (PAGE_SIZE.0 - 2.0 * PAGE_MARGIN) / columns as f32;
+        let slot_height = (PAGE_SIZE.1 - 2.0 * PAGE_MARGIN) / rows as f32;
+        let side = slot_width.min(slot_height - TITLE_SPACE) - SLOT_GAP;
+        let layout = layout.scaled(side / layout.size());
+
+        let mut content = Content::new();
+        for (idx, (title, glyphs)) in slots.enumerate() {
+            let left =
+                PAGE_MARGIN + (idx % columns) as f32 * slot_width + (slot_width - side) / 2.0;
+            let top = PAGE_MARGIN + (idx / columns) as f32 * slot_height;
+
+            // Page coordinates run up from the bottom left corner; layouts run down from the
+            // top left corner of the grid.
+            let x = |x: f32| left + x;
+            let y = |y: f32| PAGE_SIZE.1 - (top + TITLE_SPACE + y);
+
+            content
+                .begin_text()
+                .set_font(BOLD, TITLE_SIZE)
+                .next_line(x(layout.margin), PAGE_SIZE.1 - (top + TITLE_SIZE))
+                .show(Str(title.as_bytes()))
+                .end_text();
+
+            for line in layout.lines() {
+                content.rect(x(line.x), y(line.y + line.height), line.width, line.height);
+            }
+            content.fill_nonzero();
+
+            let font_size = layout.font_size();
+            for glyph in glyphs {
+                let (gx, gy) = layout.baseline(glyph.cell, DIGIT_WIDTH * font_size);
+                let digit = [b'0' + glyph.digit];
+                content
+                    .begin_text()
+                    .set_font(if glyph.bold { BOLD } else { REGULAR }, font_size)
+                    .next_line(x(gx), y(gy))
+                    .show(Str(&digit))
+                    .end_text();
+            }
+        }
+
+        let page_id = self.next_ref();
+        let content_id = self.next_ref();
+        self.pdf.stream(content_id, &content.finish());
+
+        let mut page = self.pdf.page(page_id);
+        page.parent(Self::PAGE_TREE)
+            .media_box(Rect::new(0.0, 0.0, PAGE_SIZE.0, PAGE_SIZE.1))
+            .contents(content_id);
+        page.resources()
+            .fonts()
+            .pair(REGULAR, Self::FONT)
+            .pair(BOLD, Self::BOLD_FONT);
+        drop(page);
+
+        self.pages.push(page_id);
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        let count = self.pages.len() as i32;
+        self.pdf
+            .pages(Self::PAGE_TREE)
+            .kids(self.pages.iter().copied())
+            .count(count);
+        self.pdf.finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::write;
+    use crate::{fixture, format::Options, Puzzle};
+
+    fn puzzle(id: &str) -> Puzzle {
+        Puzzle {
+            id: String::from(id),
+            ..fixture::puzzle()
+        }
+    }
+
+    #[test]
+    fn booklet_pages() {
+        let puzzles: Vec<Puzzle> = (0..7).map(|id| puzzle(&id.to_string())).collect();
+        let options = Options {
+            per_page: 4,
+            ..Options::default()
+        };
+
+        let mut pdf = Vec::new();
+        write(&puzzles, &options, &mut pdf).unwrap();
+        let text = String::from_utf8_lossy(&pdf);
+
+        // Two pages of puzzles, then two pages of answers.
+        assert!(text.starts_with("%PDF-"));
+        assert_eq!(
+            text.matches("/Type /Page\n").count() + text.matches("/Type /Page ").count(),
+            4
+        );
+        assert!(text.contains("/Count 4"));
+    }
+
+    #[test]
+    fn rejects_other_page_counts() {
+        let options = Options {
+            per_page: 3,
+            ..Options::default()
+        };
+        assert!(write(&[puzzle("1")], &options, Vec::new()).is_err());
+    }
+}
@@ -0,0 +1,192 @@
+use std::io::{self, Write};
+
+use ab_glyph::{point, Font, FontRef, PxScale, ScaleFont};
+
+use crate::{
+    render::{self, Rect},
+    Puzzle,
+};
+
+use super::Options;
+
+/// The fonts used for digits, bundled so that rendering never depends on system fonts.
+static REGULAR: &[u8] = include_bytes!("../../resource/fonts/DejaVuSans.ttf");
+static BOLD: &[u8] = include_bytes!("../../resource/fonts/DejaVuSans-Bold.ttf");
+
+/// The resolution at which one layout unit is one pixel, as for css pixels.
+const BASE_DPI: f32 = 96.0;
+
+/// The widest image drawn, in pixels, so that a typo in the resolution cannot exhaust memory.
+const MAX_SIZE: usize = 8192;
+
+/// Write a grayscale png image of the puzzle, laid out by `options.layout` and drawn at
+/// `options.dpi`.
+pub(super) fn write(puzzle: &Puzzle, options: &Options, w: impl Write) -> io::Result<()> {
+    if !options.dpi.is_finite() || options.dpi <= 0.0 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("cannot draw an image at {} dpi", options.dpi),
+        ));
+    }
+
+    let layout = options.layout.scaled(options.dpi / BASE_DPI);
+    let size = layout.size().ceil();
+    if !(1.0..=MAX_SIZE as f32).contains(&size) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+                "image would be {} pixels wide; at most {} are allowed",
+                size, MAX_SIZE
+            ),
+        ));
+    }
+    let size = size as usize;
+    let mut canvas = Canvas::new(size);
+
+    for line in layout.lines() {
+        canvas.fill(line);
+    }
+
+    let regular = FontRef::try_from_slice(REGULAR).expect("bundled font is valid");
+    let bold = FontRef::try_from_slice(BOLD).expect("bundled font is valid");
+    for glyph in render::glyphs(puzzle, options.with_solution, options.bold_givens) {
+        let font = if glyph.bold { &bold } else { &regular };
+        let scale = em_scale(font, layout.font_size());
+        let id = font.glyph_id(char::from(b'0' + glyph.digit));
+        let (x, y) = layout.baseline(glyph.cell, font.as_scaled(scale).h_advance(id));
+
+        if let Some(outline) = font.outline_glyph(id.with_scale_and_position(scale, point(x, y))) {
+            let bounds = outline.px_bounds();
+            outline.draw(|gx, gy, coverage| {
+                canvas.ink(
+                    bounds.min.x as i64 + i64::from(gx),
+                    bounds.min.y as i64 + i64::from(gy),
+                    coverage,
+                )
+            });
+        }
+    }
+
+    let mut encoder = png::Encoder::new(w, size as u32, size as u32);
+    encoder.set_color(png::ColorType::Grayscale);
+    encoder.set_depth(png::BitDepth::Eight);
+    let pixels_per_meter = (options.dpi / 0.0254).round() as u32;
+    encoder.set_pixel_dims(Some(png::PixelDimensions {
+        xppu: pixels_per_meter,
+        yppu: pixels_per_meter,
+        unit: png::Unit::Meter,
+    }));
+
+    let mut writer = encoder.write_header()?;
+    writer.write_image_data(&canvas.pixels())?;
+    writer.finish()?;
+    Ok(())
+}
+
+/// The scale at which a font's em square is `size` pixels, as with font sizes in svg.
+///
+/// `Font::pt_to_px_scale` would take the size as points, drawing digits 4/3 too large.
+fn em_scale(font: &FontRef, size: f32) -> PxScale {
+    let units_per_em = font.units_per_em().expect("bundled font has units per em");
+    PxScale::from(size * font.height_unscaled() / units_per_em)
+}
+
+/// A square image of ink coverage, from 0 (white) to 1 (black).
+struct Canvas {
+    size: usize,
+    ink: Vec<f32>,
+}
+
+impl Canvas {
+    fn new(size: usize) -> Self {
+        Self {
+            size,
+            ink: vec![0.0; size * size],
+        }
+    }
+
+    /// Add ink to a pixel, as if painting over it with the given opacity.
+    fn ink(&mut self, x: i64, y: i64, coverage: f32) {
+        let size = self.size as i64;
+        if (0..size).contains(&x) && (0..size).contains(&y) {
+            let ink = &mut self.ink[(y * size + x) as usize];
+            *ink += coverage.min(1.0) * (1.0 - *ink);
+        }
+    }
+
+    /// Fill a rectangle, antialiasing its edges.
+    fn fill(&mut self, rect: Rect) {
+        // The share of the pixel starting at `pixel` covered by the span `start..end`.
+        let overlap = |pixel: i64, start: f32, end: f32| {
+            (end.min(pixel as f32 + 1.0) - start.max(pixel as f32)).max(0.0)
+        };
+
+        let (right, bottom) = (rect.x + rect.width, rect.y + rect.height);
+        for y in rect.y.floor() as i64..bottom.ceil() as i64 {
+            for x in rect.x.floor() as i64..right.ceil() as i64 {
+                let coverage = overlap(x, rect.x, right) * overlap(y, rect.y, bottom);
+                self.ink(x, y, coverage);
+            }
+        }
+    }
+
+    fn pixels(&self) -> Vec<u8> {
+        self.ink
+            .iter()
+            .map(|ink| (255.0 * (1.0 - ink)).round() as u8)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::write;
+    use crate::{fixture, format::Options};
+
+    #[test]
+    fn renders_at_dpi() {
+        let puzzle = fixture::puzzle();
+        let options = Options {
+            dpi: 192.0,
+            ..Options::default()
+        };
+
+        let mut png = Vec::new();
+        write(&puzzle, &options, &mut png).unwrap();
+
+        let decoder = png::Decoder::new(png.as_slice());
+        let mut reader = decoder.read_info().unwrap();
+        assert_eq!(reader.info().width, 760);
+        assert_eq!(reader.info().pixel_dims.unwrap().xppu, 7559);
+
+        let mut pixels = vec![0; reader.output_buffer_size()];
+        reader.next_frame(&mut pixels).unwrap();
+        let pixel = |x: usize, y: usize| pixels[y * 760 + x];
+
+        // The outer border is black, the blank r1c2 white, and the given 7 in r1c5 inked.
+        assert_eq!(pixel(20, 300), 0);
+        assert!((30..90).all(|y| (110..170).all(|x| pixel(x, y) == 255)));
+        assert!((30..90).any(|y| (350..410).any(|x| pixel(x, y) < 128)));
+
+        // Digits are as tall as in svg: about 0.72 of the 48 pixel font size, centred.
+        let inked: Vec<usize> = (25..95)
+            .filter(|&y| (345..415).any(|x| pixel(x, y) < 128))
+            .collect();
+        let (top, bottom) = (inked[0], inked[inked.len() - 1]);
+        assert!((33..=37).contains(&(bottom - top + 1)));
+        assert!((40..=45).contains(&top));
+    }
+
+    #[test]
+    fn rejects_unusable_resolutions() {
+        let puzzle = fixture::puzzle();
+        for dpi in [0.0, -96.0, f32::NAN, f32::INFINITY, 1e9] {
+            let options = Options {
+                dpi,
+                ..Options::default()
+            };
+            let e = write(&puzzle, &options, Vec::new()).unwrap_err();
+            assert_eq!(e.kind(), std::io::ErrorKind::InvalidInput);
+        }
+    }
+}
@@ -0,0 +1,87 @@
+use std::io::{self, Write};
+
+use crate::{render, Puzzle};
+
+use super::Options;
+
+/// Write a standalone svg image of the puzzle, laid out by `options.layout`.
+pub(super) fn write(puzzle: &Puzzle, options: &Options, mut w: impl Write) -> io::Result<()> {
+    let layout = &options.layout;
+    let size = layout.size();
+
+    writeln!(
+        w,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{0}" height="{0}" viewBox="0 0 {0} {0}">"#,
+        size
+    )?;
+    writeln!(w, "<title>{}</title>", puzzle.puzzle_ref())?;
+    writeln!(w, r#"<rect width="{0}" height="{0}" fill="white"/>"#, size)?;
+
+    writeln!(w, r#"<g fill="black">"#)?;
+    for line in layout.lines() {
+        writeln!(
+            w,
+            r#"<rect x="{}" y="{}" width="{}" height="{}"/>"#,
+            line.x, line.y, line.width, line.height
+        )?;
+    }
+    writeln!(w, "</g>")?;
+
+    writeln!(
+        w,
+        r#"<g font-family="{}" font-size="{}" text-anchor="middle" fill="black">"#,
+        escape(&layout.font_family),
+        layout.font_size()
+    )?;
+    for glyph in render::glyphs(puzzle, options.with_solution, options.bold_givens) {
+        // Anchored in the middle, so the digit's own width does not matter.
+        let (x, y) = layout.baseline(glyph.cell, 0.0);
+        let weight = if glyph.bold {
+            r#" font-weight="bold""#
+        } else {
+            ""
+        };
+        writeln!(
+            w,
+            r#"<text x="{}" y="{}"{}>{}</text>"#,
+            x, y, weight, glyph.digit
+        )?;
+    }
+    writeln!(w, "</g>")?;
+
+    writeln!(w, "</svg>")
+}
+
+/// Escape text for use in an attribute value.
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod test {
+    use super::write;
+    use crate::{fixture, format::Options};
+
+    #[test]
+    fn svg_document() {
+        let puzzle = fixture::puzzle();
+        let mut options = Options {
+            with_solution: true,
+            bold_givens: true,
+            ..Options::default()
+        };
+        options.layout.font_family = String::from("\"Fira Sans\"");
+
+        let mut svg = Vec::new();
+        write(&puzzle, &options, &mut svg).unwrap();
+        let svg = String::from_utf8(svg).unwrap();
+
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"380\""));
+        assert!(svg.contains("font-family=\"&quot;Fira Sans&quot;\""));
+        assert_eq!(svg.matches("<text ").count(), 81);
+        assert_eq!(svg.matches("font-weight=\"bold\"").count(), 35);
+        assert!(svg.trim_end().ends_with("</svg>"));
+    }
+}
@@ -0,0 +1,96 @@
+use std::io::{self, Write};
+
+use crate::Puzzle;
+
+const TOP: &str = "┌───────┬───────┬───────┐";
+const MIDDLE: &str = "├───────┼───────┼───────┤";
+const BOTTOM: &str = "└───────┴───────┴───────┘";
+
+const GIVEN: &str = "\x1b[1m";
+const BLANK: &str = "\x1b[2m";
+const SOLVED: &str = "\x1b[36m";
+const RESET: &str = "\x1b[0m";
+
+/// Draw the puzzle with box-drawing characters, under a line naming it.
+///
+/// Blank cells are drawn as `.`, or filled in from the solution with `with_solution`. With
+/// `color`, givens are bold and blanks (or their solution digits) are dimmed (or cyan).
+pub(super) fn write(
+    puzzle: &Puzzle,
+    with_solution: bool,
+    color: bool,
+    mut w: impl Write,
+) -> io::Result<()> {
+    writeln!(w, "{}", puzzle.puzzle_ref())?;
+    writeln!(w, "{}", TOP)?;
+
+    for (row, (values, mask)) in puzzle
+        .solution
+        .chunks(9)
+        .zip(puzzle.mask.chunks(9))
+        .enumerate()
+    {
+        if row == 3 || row == 6 {
+            writeln!(w, "{}", MIDDLE)?;
+        }
+
+        let mut line = String::from("│");
+        for (column, (&value, &can_edit)) in values.iter().zip(mask).enumerate() {
+            if column == 3 || column == 6 {
+                line.push_str(" │");
+            }
+
+            let digit = match (can_edit, with_solution) {
+                (true, false) => '.',
+                _ => char::from(b'0' + value),
+            };
+            let style = match (can_edit, with_solution) {
+                (false, _) => GIVEN,
+                (true, false) => BLANK,
+                (true, true) => SOLVED,
+            };
+
+            line.push(' ');
+            if color {
+                line.push_str(style);
+                line.push(digit);
+                line.push_str(RESET);
+            } else {
+                line.push(digit);
+            }
+        }
+        line.push_str(" │");
+        writeln!(w, "{}", line)?;
+    }
+
+    writeln!(w, "{}", BOTTOM)
+}
+
+#[cfg(test)]
+mod test {
+    use super::write;
+    use crate::fixture::puzzle;
+
+    #[test]
+    fn draws_boxes() {
+        let mut text = Vec::new();
+        write(&puzzle(), false, false, &mut text).unwrap();
+        let text = String::from_utf8(text).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+
+        assert_eq!(lines.len(), 14);
+        assert_eq!(lines[0], "Easy 7042100266");
+        assert_eq!(lines[2], "│ . . . │ . 7 . │ 6 . . │");
+        assert_eq!(lines[5], "├───────┼───────┼───────┤");
+        assert_eq!(lines[13], "└───────┴───────┴───────┘");
+    }
+
+    #[test]
+    fn colors_givens_and_solution() {
+        let mut text = Vec::new();
+        write(&puzzle(), true, true, &mut text).unwrap();
+        let text = String::from_utf8(text).unwrap();
+
+        assert!(text.contains("│ \x1b[36m2\x1b[0m \x1b[1m7\x1b[0m \x1b[36m3\x1b[0m │"));
+    }
+}
@@ -0,0 +1,95 @@
+//! Row, column and box geometry of a 9x9 sudoku grid.
+//!
+//! Cells are addressed by their row-major index, `0..81`.
+
+use std::fmt::{self, Display};
+
+/// The number of cells in a grid.
+pub const CELLS: usize = 81;
+
+/// The row (`0..9`) containing a cell.
+pub fn row(cell: usize) -> usize {
+    cell / 9
+}
+
+/// The column (`0..9`) containing a cell.
+pub fn column(cell: usize) -> usize {
+    cell % 9
+}
+
+/// The 3x3 box (`0..9`, numbered left to right, top to bottom) containing a cell.
+pub fn box_of(cell: usize) -> usize {
+    row(cell) / 3 * 3 + column(cell) / 3
+}
+
+/// Whether two distinct cells share a row, column or box.
+pub fn sees(a: usize, b: usize) -> bool {
+    a != b && (row(a) == row(b) || column(a) == column(b) || box_of(a) == box_of(b))
+}
+
+/// The 20 cells which share a row, column or box with `cell`.
+pub fn peers(cell: usize) -> impl Iterator<Item = usize> {
+    (0..CELLS).filter(move |&other| sees(cell, other))
+}
+
+/// The conventional name of a cell, e.g. `r1c1` for the top left cell.
+pub fn cell_name(cell: usize) -> String {
+    format!("r{}c{}", row(cell) + 1, column(cell) + 1)
+}
+
+/// A row, column or box: a group of nine cells which must hold each digit exactly once.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Unit {
+    Row(usize),
+    Column(usize),
+    Box(usize),
+}
+
+impl Unit {
+    /// Every unit of the grid: nine rows, then nine columns, then nine boxes.
+    pub fn all() -> impl Iterator<Item = Unit> {
+        (0..9)
+            .map(Unit::Row)
+            .chain((0..9).map(Unit::Column))
+            .chain((0..9).map(Unit::Box))
+    }
+
+    /// The indexes of the cells in this unit.
+    pub fn cells(self) -> [usize; 9] {
+        let mut cells = [0; 9];
+        for (i, cell) in cells.iter_mut().enumerate() {
+            *cell = match self {
+                Unit::Row(row) => row * 9 + i,
+                Unit::Column(column) => i * 9 + column,
+                Unit::Box(b) => (b / 3 * 3 + i / 3) * 9 + b % 3 * 3 + i % 3,
+            };
+        }
+        cells
+    }
+}
+
+impl Display for Unit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Unit::Row(row) => write!(f, "row {}", row + 1),
+            Unit::Column(column) => write!(f, "column {}", column + 1),
+            Unit::Box(b) => write!(f, "box {}", b + 1),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{box_of, peers, Unit};
+
+    #[test]
+    fn box_cells() {
+        assert_eq!(Unit::Box(4).cells(), [30, 31, 32, 39, 40, 41, 48, 49, 50]);
+        assert!(Unit::Box(8).cells().iter().all(|&cell| box_of(cell) == 8));
+    }
+
+    #[test]
+    fn every_cell_has_twenty_peers() {
+        assert!((0..81).all(|cell| peers(cell).count() == 20));
+    }
+}
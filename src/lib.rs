@@ -0,0 +1,46 @@
+//! Download and extract puzzles from [websudoku](https://www.websudoku.com).
+//!
+//! Downloading a puzzle takes three steps: resolve user input to a [`PuzzleRef`], fetch the
+//! page with a [`Fetcher`], and pull the puzzle data out of that page with a
+//! [`PuzzleExtractor`]. Every step reports failures through [`Error`]. A [`Downloader`]
+//! performs the last two steps for any number of puzzles.
+//!
+//! ```no_run
+//! use websudoku::{Fetcher, PuzzleExtractor, Resolver};
+//!
+//! # fn main() -> websudoku::Result<()> {
+//! let puzzle_ref = Resolver::new().resolve("7042100266", None)?;
+//! let content = Fetcher::new()?.fetch(&puzzle_ref)?;
+//! let puzzle = PuzzleExtractor::new().extract(puzzle_ref.difficulty, &content)?;
+//!
+//! puzzle.write_masked_puzzle(std::io::stdout())?;
+//! # Ok(())
+//! # }
+//! ```
+
+pub mod batch;
+mod difficulty;
+mod error;
+mod extract;
+mod fetch;
+#[cfg(test)]
+mod fixture;
+pub mod format;
+pub mod grid;
+pub mod logic;
+mod output;
+pub mod play;
+mod puzzle;
+pub mod render;
+mod resolve;
+pub mod solver;
+pub mod verify;
+
+pub use difficulty::Difficulty;
+pub use error::{Error, Field, Result};
+pub use extract::PuzzleExtractor;
+pub use fetch::{Downloader, Fetcher};
+pub use format::Format;
+pub use output::Output;
+pub use puzzle::Puzzle;
+pub use resolve::{PuzzleRef, Resolver};
@@ -0,0 +1,85 @@
+use crate::grid::{self, Unit, CELLS};
+
+/// A partially solved grid with pencil marks for every blank cell.
+#[derive(Clone, Debug)]
+pub(crate) struct Board {
+    values: [u8; CELLS],
+    candidates: [u16; CELLS],
+}
+
+impl Board {
+    /// Build a board from givens, or `None` if the givens contradict each other.
+    pub fn new(givens: &[u8]) -> Option<Self> {
+        if givens.len() != CELLS {
+            return None;
+        }
+
+        let mut board = Board {
+            values: [0; CELLS],
+            candidates: [ALL; CELLS],
+        };
+
+        for (cell, &value) in givens.iter().enumerate() {
+            match value {
+                0 => {}
+                1..=9 if board.has(cell, value) => board.place(cell, value),
+                _ => return None,
+            }
+        }
+
+        Some(board)
+    }
+
+    pub fn values(&self) -> &[u8] {
+        &self.values
+    }
+
+    pub fn value(&self, cell: usize) -> u8 {
+        self.values[cell]
+    }
+
+    /// The candidates of a blank cell as a bit set; solved cells have none.
+    pub fn candidates(&self, cell: usize) -> u16 {
+        self.candidates[cell]
+    }
+
+    pub fn has(&self, cell: usize, digit: u8) -> bool {
+        self.candidates[cell] & bit(digit) != 0
+    }
+
+    pub fn is_solved(&self) -> bool {
+        self.values.iter().all(|&value| value != 0)
+    }
+
+    /// The blank cells of `unit` where `digit` is still a candidate.
+    pub fn cells_with(&self, unit: Unit, digit: u8) -> Vec<usize> {
+        unit.cells()
+            .iter()
+            .copied()
+            .filter(|&cell| self.has(cell, digit))
+            .collect()
+    }
+
+    pub fn place(&mut self, cell: usize, digit: u8) {
+        self.values[cell] = digit;
+        self.candidates[cell] = 0;
+        for peer in grid::peers(cell) {
+            self.eliminate(peer, digit);
+        }
+    }
+
+    pub fn eliminate(&mut self, cell: usize, digit: u8) {
+        self.candidates[cell] &= !bit(digit);
+    }
+}
+
+const ALL: u16 = 0x3fe;
+
+pub(crate) fn bit(digit: u8) -> u16 {
+    1 << digit
+}
+
+/// The digits in a candidate bit set, in ascending order.
+pub(crate) fn digits(candidates: u16) -> impl Iterator<Item = u8> {
+    (1..=9).filter(move |&digit| candidates & bit(digit) != 0)
+}
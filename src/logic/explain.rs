@@ -0,0 +1,256 @@
+//! Walkthroughs of a logical solution, for reading or for building teaching material.
+
+use std::{
+    fmt::{self, Display},
+    io::{self, Write},
+    str::FromStr,
+};
+
+use serde::Serialize;
+
+use crate::{grid, Difficulty, Puzzle};
+
+use super::{Candidate, Solution, Step};
+
+/// An output format for walkthroughs.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum Format {
+    /// A heading, the starting grid and one numbered line per step.
+    #[default]
+    Text,
+    /// The same as text, with a Markdown heading, code block and list.
+    Markdown,
+    /// A JSON object with the rating and every step's cells, placements and eliminations.
+    Json,
+}
+
+impl Format {
+    /// The file extension used for this format.
+    pub fn extension(self) -> &'static str {
+        match self {
+            Format::Text => "txt",
+            Format::Markdown => "md",
+            Format::Json => "json",
+        }
+    }
+
+    /// Write the walkthrough of a single puzzle as a complete document.
+    pub fn write(self, puzzle: &Puzzle, solution: &Solution, mut w: impl Write) -> io::Result<()> {
+        match self {
+            Format::Text => write_text(puzzle, solution, w),
+            Format::Markdown => write_markdown(puzzle, solution, w),
+            Format::Json => {
+                serde_json::to_writer_pretty(&mut w, &Document::new(puzzle, solution))?;
+                writeln!(w)
+            }
+        }
+    }
+
+    /// Write several walkthroughs to one stream.
+    ///
+    /// JSON is written as JSON Lines, one compact object per puzzle; text and Markdown
+    /// walkthroughs are separated by a blank line.
+    pub fn write_many<'a>(
+        self,
+        walkthroughs: impl IntoIterator<Item = (&'a Puzzle, &'a Solution)>,
+        mut w: impl Write,
+    ) -> io::Result<()> {
+        for (idx, (puzzle, solution)) in walkthroughs.into_iter().enumerate() {
+            match self {
+                Format::Json => {
+                    serde_json::to_writer(&mut w, &Document::new(puzzle, solution))?;
+                    writeln!(w)?;
+                }
+                _ => {
+                    if idx > 0 {
+                        writeln!(w)?;
+                    }
+                    self.write(puzzle, solution, &mut w)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Format::Text => "text",
+            Format::Markdown => "markdown",
+            Format::Json => "json",
+        })
+    }
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "text" | "txt" => Ok(Format::Text),
+            "markdown" | "md" => Ok(Format::Markdown),
+            "json" => Ok(Format::Json),
+            _ => Err(format!("Unrecognized explanation format: {}", s)),
+        }
+    }
+}
+
+fn write_text(puzzle: &Puzzle, solution: &Solution, mut w: impl Write) -> io::Result<()> {
+    writeln!(w, "{}", puzzle.puzzle_ref())?;
+    writeln!(w, "Rating: {}", solution.rating())?;
+    writeln!(w)?;
+    write_grid(puzzle, &mut w)?;
+    writeln!(w)?;
+
+    let width = solution.steps.len().to_string().len();
+    for (idx, step) in solution.steps.iter().enumerate() {
+        writeln!(w, "{:>width$}. {}", idx + 1, step, width = width)?;
+    }
+    Ok(())
+}
+
+fn write_markdown(puzzle: &Puzzle, solution: &Solution, mut w: impl Write) -> io::Result<()> {
+    writeln!(w, "# {}", puzzle.puzzle_ref())?;
+    writeln!(w)?;
+    writeln!(w, "Rating: {}", solution.rating())?;
+    writeln!(w)?;
+    writeln!(w, "```text")?;
+    write_grid(puzzle, &mut w)?;
+    writeln!(w, "```")?;
+    writeln!(w)?;
+
+    for (idx, step) in solution.steps.iter().enumerate() {
+        writeln!(w, "{}. **{}**{}", idx + 1, step.technique, step.details())?;
+    }
+    Ok(())
+}
+
+/// The givens as nine rows of nine characters, with `.` for blanks.
+fn write_grid(puzzle: &Puzzle, mut w: impl Write) -> io::Result<()> {
+    for row in puzzle.givens().chunks(9) {
+        let row: String = row
+            .iter()
+            .map(|&digit| match digit {
+                0 => '.',
+                digit => (b'0' + digit) as char,
+            })
+            .collect();
+        writeln!(w, "{}", row)?;
+    }
+    Ok(())
+}
+
+/// The JSON representation of a walkthrough.
+///
+/// Cells are named as in the text formats (`r1c1` to `r9c9`) so steps can be read without
+/// converting indexes.
+#[derive(Serialize)]
+struct Document<'a> {
+    id: &'a str,
+    difficulty: Difficulty,
+    url: String,
+    score: f32,
+    hardest: Option<String>,
+    steps: Vec<StepDocument>,
+}
+
+#[derive(Serialize)]
+struct StepDocument {
+    technique: String,
+    description: String,
+    digits: Vec<u8>,
+    cells: Vec<String>,
+    units: Vec<String>,
+    placements: Vec<CandidateDocument>,
+    eliminations: Vec<CandidateDocument>,
+}
+
+#[derive(Serialize)]
+struct CandidateDocument {
+    cell: String,
+    digit: u8,
+}
+
+impl<'a> Document<'a> {
+    fn new(puzzle: &'a Puzzle, solution: &Solution) -> Self {
+        let rating = solution.rating();
+        Self {
+            id: &puzzle.id,
+            difficulty: puzzle.difficulty,
+            url: puzzle.puzzle_ref().url(),
+            score: rating.score,
+            hardest: rating.hardest.map(|technique| technique.to_string()),
+            steps: solution.steps.iter().map(StepDocument::new).collect(),
+        }
+    }
+}
+
+impl StepDocument {
+    fn new(step: &Step) -> Self {
+        let candidates = |candidates: &[Candidate]| {
+            candidates
+                .iter()
+                .map(|candidate| CandidateDocument {
+                    cell: grid::cell_name(candidate.cell),
+                    digit: candidate.digit,
+                })
+                .collect()
+        };
+
+        Self {
+            technique: step.technique.to_string(),
+            description: step.to_string(),
+            digits: step.digits.clone(),
+            cells: step
+                .cells
+                .iter()
+                .map(|&cell| grid::cell_name(cell))
+                .collect(),
+            units: step.units.iter().map(ToString::to_string).collect(),
+            placements: candidates(&step.placements),
+            eliminations: candidates(&step.eliminations),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Format;
+    use crate::{fixture, logic, Puzzle};
+
+    fn puzzle() -> Puzzle {
+        let mut puzzle = fixture::puzzle();
+        // Two blanks in the first row.
+        puzzle.mask = (0..81).map(|i| i == 0 || i == 1).collect();
+        puzzle
+    }
+
+    #[test]
+    fn text_walkthrough() {
+        let puzzle = puzzle();
+        let solution = logic::solve(&puzzle.givens()).unwrap();
+
+        let mut text = Vec::new();
+        Format::Text.write(&puzzle, &solution, &mut text).unwrap();
+        let text = String::from_utf8(text).unwrap();
+
+        assert!(text
+            .starts_with("Easy 7042100266\nRating: 1.5 (Hidden Single, 2 steps)\n\n..4273651\n"));
+        assert!(text.ends_with("1. Hidden Single in box 1: place 8 in r1c2\n2. Hidden Single in box 1: place 9 in r1c1\n"));
+    }
+
+    #[test]
+    fn json_names_cells() {
+        let puzzle = puzzle();
+        let solution = logic::solve(&puzzle.givens()).unwrap();
+
+        let mut json = Vec::new();
+        Format::Json.write(&puzzle, &solution, &mut json).unwrap();
+        let value: serde_json::Value = serde_json::from_slice(&json).unwrap();
+
+        assert_eq!(value["hardest"], "Hidden Single");
+        assert_eq!(value["steps"][0]["placements"][0]["cell"], "r1c2");
+        assert_eq!(value["steps"][0]["units"][0], "box 1");
+    }
+}
@@ -0,0 +1,384 @@
+//! A solver which works the way a person does, one named technique at a time.
+//!
+//! Unlike [`solver`](crate::solver), which searches blindly, this solver only makes
+//! deductions a human could follow. The techniques a puzzle requires give a much better
+//! measure of its difficulty than the level websudoku files it under.
+
+mod board;
+pub mod explain;
+mod techniques;
+
+use std::fmt::{self, Display};
+
+use crate::{
+    error::{Error, Result},
+    grid::{self, Unit},
+    solver,
+};
+
+use board::Board;
+
+/// A named solving technique, in roughly increasing order of difficulty.
+#[derive(Copy, Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Technique {
+    HiddenSingle,
+    NakedSingle,
+    Pointing,
+    Claiming,
+    NakedPair,
+    XWing,
+    HiddenPair,
+    NakedTriple,
+    Swordfish,
+    HiddenTriple,
+    XyWing,
+    XyzWing,
+    NakedQuad,
+    Jellyfish,
+    HiddenQuad,
+    XChain,
+    XyChain,
+    /// Trial and error: no known technique applies, so a digit is taken from the solution.
+    Guess,
+}
+
+impl Technique {
+    /// The difficulty of the technique, on a scale similar to Sudoku Explainer's.
+    pub fn rating(self) -> f32 {
+        match self {
+            Technique::HiddenSingle => 1.5,
+            Technique::NakedSingle => 2.3,
+            Technique::Pointing => 2.6,
+            Technique::Claiming => 2.8,
+            Technique::NakedPair => 3.0,
+            Technique::XWing => 3.2,
+            Technique::HiddenPair => 3.4,
+            Technique::NakedTriple => 3.6,
+            Technique::Swordfish => 3.8,
+            Technique::HiddenTriple => 4.0,
+            Technique::XyWing => 4.2,
+            Technique::XyzWing => 4.4,
+            Technique::NakedQuad => 5.0,
+            Technique::Jellyfish => 5.2,
+            Technique::HiddenQuad => 5.4,
+            Technique::XChain => 6.6,
+            Technique::XyChain => 7.0,
+            Technique::Guess => 10.0,
+        }
+    }
+}
+
+impl Display for Technique {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Technique::HiddenSingle => "Hidden Single",
+            Technique::NakedSingle => "Naked Single",
+            Technique::Pointing => "Pointing",
+            Technique::Claiming => "Claiming",
+            Technique::NakedPair => "Naked Pair",
+            Technique::XWing => "X-Wing",
+            Technique::HiddenPair => "Hidden Pair",
+            Technique::NakedTriple => "Naked Triple",
+            Technique::Swordfish => "Swordfish",
+            Technique::HiddenTriple => "Hidden Triple",
+            Technique::XyWing => "XY-Wing",
+            Technique::XyzWing => "XYZ-Wing",
+            Technique::NakedQuad => "Naked Quad",
+            Technique::Jellyfish => "Jellyfish",
+            Technique::HiddenQuad => "Hidden Quad",
+            Technique::XChain => "X-Chain",
+            Technique::XyChain => "XY-Chain",
+            Technique::Guess => "Guess",
+        })
+    }
+}
+
+/// A digit in a cell: either placed there or removed from its candidates.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Candidate {
+    pub cell: usize,
+    pub digit: u8,
+}
+
+/// One deduction: the technique used, the pattern it found, and what it proved.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Step {
+    pub technique: Technique,
+    /// The digits the pattern is built on.
+    pub digits: Vec<u8>,
+    /// The cells forming the pattern, in chain order for chains.
+    pub cells: Vec<usize>,
+    /// The units the pattern lives in, if it is defined by units.
+    pub units: Vec<Unit>,
+    pub placements: Vec<Candidate>,
+    pub eliminations: Vec<Candidate>,
+}
+
+impl Step {
+    /// Describe the pattern and what it proves, without naming the technique.
+    ///
+    /// For example, ` in box 2: place 7 in r3c4` or ` 4/7 in row 3 (r3c1, r3c5): remove 4
+    /// from r3c6`.
+    pub fn details(&self) -> String {
+        let mut details = String::new();
+
+        // Singles are fully described by their placement.
+        if !self.eliminations.is_empty() {
+            let digits: Vec<String> = self.digits.iter().map(u8::to_string).collect();
+            details += &format!(" {}", digits.join("/"));
+        }
+        if !self.units.is_empty() {
+            details += &format!(" in {}", join(&self.units, |unit| unit.to_string()));
+        }
+        if !self.eliminations.is_empty() {
+            details += &format!(" ({})", join(&self.cells, |&cell| grid::cell_name(cell)));
+        }
+
+        let mut actions = Vec::new();
+        for placement in &self.placements {
+            actions.push(format!(
+                "place {} in {}",
+                placement.digit,
+                grid::cell_name(placement.cell)
+            ));
+        }
+        for digit in 1..=9 {
+            let cells: Vec<usize> = self
+                .eliminations
+                .iter()
+                .filter(|elimination| elimination.digit == digit)
+                .map(|elimination| elimination.cell)
+                .collect();
+            if !cells.is_empty() {
+                actions.push(format!(
+                    "remove {} from {}",
+                    digit,
+                    join(&cells, |&cell| grid::cell_name(cell))
+                ));
+            }
+        }
+
+        details + ": " + &actions.join("; ")
+    }
+
+    fn apply(&self, board: &mut Board) {
+        for placement in &self.placements {
+            board.place(placement.cell, placement.digit);
+        }
+        for elimination in &self.eliminations {
+            board.eliminate(elimination.cell, elimination.digit);
+        }
+    }
+}
+
+impl Display for Step {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}{}", self.technique, self.details())
+    }
+}
+
+fn join<T>(items: &[T], name: impl Fn(&T) -> String) -> String {
+    items.iter().map(name).collect::<Vec<_>>().join(", ")
+}
+
+/// A logical solution: every step taken from the givens to the completed grid.
+#[derive(Clone, Debug)]
+pub struct Solution {
+    pub steps: Vec<Step>,
+    pub grid: Vec<u8>,
+}
+
+/// How hard a puzzle is to solve by hand.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Rating {
+    /// The rating of the hardest technique required, or 0 for a grid with no blanks.
+    pub score: f32,
+    pub hardest: Option<Technique>,
+    pub steps: usize,
+}
+
+impl Display for Rating {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.hardest {
+            Some(hardest) => write!(f, "{:.1} ({}, {} steps)", self.score, hardest, self.steps),
+            None => write!(f, "{:.1} (already solved)", self.score),
+        }
+    }
+}
+
+impl Solution {
+    pub fn rating(&self) -> Rating {
+        let hardest = self.steps.iter().map(|step| step.technique).max();
+        Rating {
+            score: hardest.map_or(0.0, Technique::rating),
+            hardest,
+            steps: self.steps.len(),
+        }
+    }
+}
+
+/// Solve a grid of givens (`0` for blanks) using the easiest technique available at each
+/// step.
+///
+/// When no technique applies, the solver falls back on [`Technique::Guess`], so any
+/// solvable grid is completed. Givens which cannot be completed are `Error::Unsolvable`.
+pub fn solve(givens: &[u8]) -> Result<Solution> {
+    let mut board = Board::new(givens).ok_or(Error::Unsolvable)?;
+    let mut steps = Vec::new();
+
+    while !board.is_solved() {
+        let step = find_step(&board)?;
+        step.apply(&mut board);
+        steps.push(step);
+    }
+
+    Ok(Solution {
+        steps,
+        grid: board.values().to_vec(),
+    })
+}
+
+/// The easiest step from a partially completed grid (`0` for blanks), or `None` if the grid
+/// is already complete.
+///
+/// Entries are trusted: a grid with a wrong entry may still yield a step, but one which
+/// leads to a contradiction. Check entries against the solution first.
+pub fn next_step(grid: &[u8]) -> Result<Option<Step>> {
+    let board = Board::new(grid).ok_or(Error::Unsolvable)?;
+    if board.is_solved() {
+        return Ok(None);
+    }
+    find_step(&board).map(Some)
+}
+
+fn find_step(board: &Board) -> Result<Step> {
+    techniques::FINDERS
+        .iter()
+        .find_map(|find| find(board))
+        .or_else(|| guess(board))
+        .ok_or(Error::Unsolvable)
+}
+
+/// Place the solution's digit in the blank cell with the fewest candidates.
+fn guess(board: &Board) -> Option<Step> {
+    let solution = solver::solve(board.values())?;
+    let cell = (0..solution.len())
+        .filter(|&cell| board.value(cell) == 0)
+        .min_by_key(|&cell| board.candidates(cell).count_ones())?;
+
+    Some(Step {
+        technique: Technique::Guess,
+        digits: vec![solution[cell]],
+        cells: vec![cell],
+        units: Vec::new(),
+        placements: vec![Candidate {
+            cell,
+            digit: solution[cell],
+        }],
+        eliminations: Vec::new(),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::{next_step, solve, Candidate, Step, Technique};
+    use crate::{fixture, grid::Unit, solver};
+
+    /// Remove givens from a solved grid in a pseudo-random order while the puzzle stays
+    /// unique, producing minimal puzzles of varied difficulty.
+    fn minimal_puzzle(solution: &[u8], seed: u64) -> Vec<u8> {
+        let mut state = seed;
+        let mut order: Vec<usize> = (0..81).collect();
+        for idx in (1..order.len()).rev() {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            order.swap(idx, (state >> 33) as usize % (idx + 1));
+        }
+
+        let mut givens = solution.to_vec();
+        for cell in order {
+            let value = givens[cell];
+            givens[cell] = 0;
+            if solver::count_solutions(&givens, 2) != 1 {
+                givens[cell] = value;
+            }
+        }
+        givens
+    }
+
+    #[test]
+    fn singles_solve_easy_puzzle() {
+        let givens = fixture::puzzle().givens();
+        let solution = solve(&givens).unwrap();
+
+        assert_eq!(solution.grid, solver::solve(&givens).unwrap());
+        assert!(solution.rating().hardest <= Some(Technique::NakedSingle));
+    }
+
+    #[test]
+    fn every_deduction_is_sound() {
+        let solved = fixture::solution();
+
+        for seed in 0..24 {
+            let givens = minimal_puzzle(&solved, seed);
+            let solution = solve(&givens).unwrap();
+
+            assert_eq!(solution.grid, solved);
+            for step in &solution.steps {
+                for placement in &step.placements {
+                    assert_eq!(solved[placement.cell], placement.digit, "{:?}", step);
+                }
+                for elimination in &step.eliminations {
+                    assert_ne!(solved[elimination.cell], elimination.digit, "{:?}", step);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn next_step_continues_from_entries() {
+        let solved = fixture::solution();
+        let mut grid = solved.clone();
+        grid[0] = 0;
+
+        let step = next_step(&grid).unwrap().unwrap();
+        assert_eq!(step.placements, [Candidate { cell: 0, digit: 9 }]);
+        assert_eq!(next_step(&solved).unwrap(), None);
+    }
+
+    #[test]
+    fn step_description() {
+        let step = Step {
+            technique: Technique::Pointing,
+            digits: vec![5],
+            cells: vec![1, 2],
+            units: vec![Unit::Box(0), Unit::Row(0)],
+            placements: Vec::new(),
+            eliminations: vec![
+                Candidate { cell: 4, digit: 5 },
+                Candidate { cell: 7, digit: 5 },
+            ],
+        };
+
+        assert_eq!(
+            step.to_string(),
+            "Pointing 5 in box 1, row 1 (r1c2, r1c3): remove 5 from r1c5, r1c8"
+        );
+    }
+
+    #[test]
+    fn contradictions_are_unsolvable() {
+        // Box 1 holds every digit but 2, and r3c7 rules 2 out of the box's last cell.
+        let mut givens = vec![0; 81];
+        for (&cell, &digit) in [0, 1, 2, 9, 10, 11, 18, 19]
+            .iter()
+            .zip(&[1, 3, 4, 5, 6, 7, 8, 9])
+        {
+            givens[cell] = digit;
+        }
+        givens[24] = 2;
+
+        assert!(solve(&givens).is_err());
+    }
+}
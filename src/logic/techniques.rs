@@ -0,0 +1,593 @@
+//! Pattern finders for each technique.
+//!
+//! Every finder returns the first instance of its pattern which places a digit or removes a
+//! candidate, so applying a step always makes progress.
+
+use crate::grid::{self, Unit, CELLS};
+
+use super::{
+    board::{bit, digits, Board},
+    Candidate, Step, Technique,
+};
+
+type Finder = fn(&Board) -> Option<Step>;
+
+/// The base lines of a fish, its cover lines, and a cell's position along the base lines.
+type Orientation = (fn(usize) -> Unit, fn(usize) -> Unit, fn(usize) -> usize);
+
+/// Every finder, easiest first.
+pub(super) const FINDERS: &[Finder] = &[
+    hidden_single,
+    naked_single,
+    pointing,
+    claiming,
+    |board| naked_subset(board, 2),
+    |board| fish(board, 2),
+    |board| hidden_subset(board, 2),
+    |board| naked_subset(board, 3),
+    |board| fish(board, 3),
+    |board| hidden_subset(board, 3),
+    xy_wing,
+    xyz_wing,
+    |board| naked_subset(board, 4),
+    |board| fish(board, 4),
+    |board| hidden_subset(board, 4),
+    x_chain,
+    xy_chain,
+];
+
+/// The longest chain, in links, that the chain finders will consider.
+const MAX_CHAIN: usize = 9;
+
+fn hidden_single(board: &Board) -> Option<Step> {
+    for unit in box_first_units() {
+        for digit in 1..=9 {
+            if let [cell] = board.cells_with(unit, digit)[..] {
+                return Some(Step {
+                    technique: Technique::HiddenSingle,
+                    digits: vec![digit],
+                    cells: vec![cell],
+                    units: vec![unit],
+                    placements: vec![Candidate { cell, digit }],
+                    eliminations: Vec::new(),
+                });
+            }
+        }
+    }
+    None
+}
+
+fn naked_single(board: &Board) -> Option<Step> {
+    let cell = (0..CELLS).find(|&cell| board.candidates(cell).count_ones() == 1)?;
+    let digit = digits(board.candidates(cell)).next()?;
+
+    Some(Step {
+        technique: Technique::NakedSingle,
+        digits: vec![digit],
+        cells: vec![cell],
+        units: Vec::new(),
+        placements: vec![Candidate { cell, digit }],
+        eliminations: Vec::new(),
+    })
+}
+
+/// A digit confined to one row or column of a box can be removed from the rest of that line.
+fn pointing(board: &Board) -> Option<Step> {
+    for b in 0..9 {
+        for digit in 1..=9 {
+            let cells = board.cells_with(Unit::Box(b), digit);
+            if cells.len() < 2 {
+                continue;
+            }
+
+            for line in shared_lines(&cells) {
+                let eliminations = eliminate(board, digit, line.cells().iter().copied(), &cells);
+                if !eliminations.is_empty() {
+                    return Some(Step {
+                        technique: Technique::Pointing,
+                        digits: vec![digit],
+                        cells,
+                        units: vec![Unit::Box(b), line],
+                        placements: Vec::new(),
+                        eliminations,
+                    });
+                }
+            }
+        }
+    }
+    None
+}
+
+/// A digit confined to one box within a row or column can be removed from the rest of that
+/// box.
+fn claiming(board: &Board) -> Option<Step> {
+    for line in lines() {
+        for digit in 1..=9 {
+            let cells = board.cells_with(line, digit);
+            if cells.len() < 2 {
+                continue;
+            }
+
+            let b = grid::box_of(cells[0]);
+            if cells.iter().any(|&cell| grid::box_of(cell) != b) {
+                continue;
+            }
+
+            let eliminations =
+                eliminate(board, digit, Unit::Box(b).cells().iter().copied(), &cells);
+            if !eliminations.is_empty() {
+                return Some(Step {
+                    technique: Technique::Claiming,
+                    digits: vec![digit],
+                    cells,
+                    units: vec![line, Unit::Box(b)],
+                    placements: Vec::new(),
+                    eliminations,
+                });
+            }
+        }
+    }
+    None
+}
+
+/// `size` cells of a unit holding only `size` digits between them claim those digits.
+fn naked_subset(board: &Board, size: usize) -> Option<Step> {
+    let technique = match size {
+        2 => Technique::NakedPair,
+        3 => Technique::NakedTriple,
+        _ => Technique::NakedQuad,
+    };
+
+    for unit in Unit::all() {
+        let open: Vec<usize> = unit
+            .cells()
+            .iter()
+            .copied()
+            .filter(|&cell| (2..=size as u32).contains(&board.candidates(cell).count_ones()))
+            .collect();
+
+        for cells in combinations(&open, size) {
+            let union = cells
+                .iter()
+                .fold(0, |union, &cell| union | board.candidates(cell));
+            if union.count_ones() as usize != size {
+                continue;
+            }
+
+            let mut eliminations = Vec::new();
+            for digit in digits(union) {
+                eliminations.extend(eliminate(
+                    board,
+                    digit,
+                    unit.cells().iter().copied(),
+                    &cells,
+                ));
+            }
+
+            if !eliminations.is_empty() {
+                return Some(Step {
+                    technique,
+                    digits: digits(union).collect(),
+                    cells,
+                    units: vec![unit],
+                    placements: Vec::new(),
+                    eliminations,
+                });
+            }
+        }
+    }
+    None
+}
+
+/// `size` digits confined to `size` cells of a unit rule out every other digit in them.
+fn hidden_subset(board: &Board, size: usize) -> Option<Step> {
+    let technique = match size {
+        2 => Technique::HiddenPair,
+        3 => Technique::HiddenTriple,
+        _ => Technique::HiddenQuad,
+    };
+
+    for unit in Unit::all() {
+        let open: Vec<usize> = (1..=9)
+            .filter(|&digit| (2..=size).contains(&board.cells_with(unit, digit as u8).len()))
+            .collect();
+
+        for combination in combinations(&open, size) {
+            let subset: Vec<u8> = combination.iter().map(|&digit| digit as u8).collect();
+            let mask = subset.iter().fold(0, |mask, &digit| mask | bit(digit));
+
+            let cells: Vec<usize> = unit
+                .cells()
+                .iter()
+                .copied()
+                .filter(|&cell| board.candidates(cell) & mask != 0)
+                .collect();
+            if cells.len() != size {
+                continue;
+            }
+
+            let eliminations: Vec<Candidate> = cells
+                .iter()
+                .flat_map(|&cell| {
+                    digits(board.candidates(cell) & !mask)
+                        .map(move |digit| Candidate { cell, digit })
+                })
+                .collect();
+
+            if !eliminations.is_empty() {
+                return Some(Step {
+                    technique,
+                    digits: subset,
+                    cells,
+                    units: vec![unit],
+                    placements: Vec::new(),
+                    eliminations,
+                });
+            }
+        }
+    }
+    None
+}
+
+/// X-Wing, Swordfish and Jellyfish: a digit confined to the same `size` columns within
+/// `size` rows (or vice versa) can be removed from the rest of those columns.
+fn fish(board: &Board, size: usize) -> Option<Step> {
+    let technique = match size {
+        2 => Technique::XWing,
+        3 => Technique::Swordfish,
+        _ => Technique::Jellyfish,
+    };
+
+    let orientations: [Orientation; 2] = [
+        (Unit::Row, Unit::Column, grid::column),
+        (Unit::Column, Unit::Row, grid::row),
+    ];
+
+    for digit in 1..=9 {
+        for &(base, cover, position) in &orientations {
+            let open: Vec<usize> = (0..9)
+                .filter(|&line| (2..=size).contains(&board.cells_with(base(line), digit).len()))
+                .collect();
+
+            for lines in combinations(&open, size) {
+                let cells: Vec<usize> = lines
+                    .iter()
+                    .flat_map(|&line| board.cells_with(base(line), digit))
+                    .collect();
+
+                let mut covers: Vec<usize> = cells.iter().map(|&cell| position(cell)).collect();
+                covers.sort_unstable();
+                covers.dedup();
+                if covers.len() != size {
+                    continue;
+                }
+
+                let eliminations: Vec<Candidate> = covers
+                    .iter()
+                    .flat_map(|&line| {
+                        eliminate(board, digit, cover(line).cells().iter().copied(), &cells)
+                    })
+                    .collect();
+
+                if !eliminations.is_empty() {
+                    return Some(Step {
+                        technique,
+                        digits: vec![digit],
+                        cells,
+                        units: lines
+                            .iter()
+                            .map(|&line| base(line))
+                            .chain(covers.iter().map(|&line| cover(line)))
+                            .collect(),
+                        placements: Vec::new(),
+                        eliminations,
+                    });
+                }
+            }
+        }
+    }
+    None
+}
+
+/// A pivot `{x,y}` sees pincers `{x,z}` and `{y,z}`: one pincer must be `z`, so cells seeing
+/// both pincers cannot be.
+fn xy_wing(board: &Board) -> Option<Step> {
+    for pivot in bivalue_cells(board) {
+        let pivot_candidates = board.candidates(pivot);
+        let wings: Vec<usize> = bivalue_cells(board)
+            .filter(|&cell| grid::sees(pivot, cell))
+            .filter(|&cell| (board.candidates(cell) & pivot_candidates).count_ones() == 1)
+            .collect();
+
+        for (idx, &a) in wings.iter().enumerate() {
+            for &b in &wings[idx + 1..] {
+                let (a_candidates, b_candidates) = (board.candidates(a), board.candidates(b));
+                let z = a_candidates & b_candidates & !pivot_candidates;
+                if z.count_ones() != 1
+                    || a_candidates & pivot_candidates == b_candidates & pivot_candidates
+                    || a_candidates | b_candidates | pivot_candidates != pivot_candidates | z
+                {
+                    continue;
+                }
+
+                let digit = digits(z).next()?;
+                let eliminations = eliminate_seen_by(board, digit, &[a, b], &[pivot, a, b]);
+                if !eliminations.is_empty() {
+                    return Some(Step {
+                        technique: Technique::XyWing,
+                        digits: digits(pivot_candidates | z).collect(),
+                        cells: vec![pivot, a, b],
+                        units: Vec::new(),
+                        placements: Vec::new(),
+                        eliminations,
+                    });
+                }
+            }
+        }
+    }
+    None
+}
+
+/// A pivot `{x,y,z}` sees pincers `{x,z}` and `{y,z}`: one of the three must be `z`, so cells
+/// seeing all three cannot be.
+fn xyz_wing(board: &Board) -> Option<Step> {
+    for pivot in (0..CELLS).filter(|&cell| board.candidates(cell).count_ones() == 3) {
+        let pivot_candidates = board.candidates(pivot);
+        let wings: Vec<usize> = bivalue_cells(board)
+            .filter(|&cell| grid::sees(pivot, cell))
+            .filter(|&cell| board.candidates(cell) & !pivot_candidates == 0)
+            .collect();
+
+        for (idx, &a) in wings.iter().enumerate() {
+            for &b in &wings[idx + 1..] {
+                let z = board.candidates(a) & board.candidates(b);
+                if z.count_ones() != 1 {
+                    continue;
+                }
+
+                let digit = digits(z).next()?;
+                let eliminations = eliminate_seen_by(board, digit, &[pivot, a, b], &[pivot, a, b]);
+                if !eliminations.is_empty() {
+                    return Some(Step {
+                        technique: Technique::XyzWing,
+                        digits: digits(pivot_candidates).collect(),
+                        cells: vec![pivot, a, b],
+                        units: Vec::new(),
+                        placements: Vec::new(),
+                        eliminations,
+                    });
+                }
+            }
+        }
+    }
+    None
+}
+
+/// A single-digit chain of alternating strong and weak links, starting and ending with a
+/// strong link: one end of the chain must hold the digit, so cells seeing both ends cannot.
+fn x_chain(board: &Board) -> Option<Step> {
+    for links in (3..=MAX_CHAIN).step_by(2) {
+        for digit in 1..=9 {
+            let strong = strong_links(board, digit);
+            for start in (0..CELLS).filter(|&cell| board.has(cell, digit)) {
+                let mut path = vec![start];
+                if let Some(step) = extend_x_chain(board, digit, &strong, &mut path, links) {
+                    return Some(step);
+                }
+            }
+        }
+    }
+    None
+}
+
+fn extend_x_chain(
+    board: &Board,
+    digit: u8,
+    strong: &[Vec<usize>],
+    path: &mut Vec<usize>,
+    links: usize,
+) -> Option<Step> {
+    let last = *path.last()?;
+    let made = path.len() - 1;
+
+    if made == links {
+        let eliminations = eliminate_seen_by(board, digit, &[path[0], last], path);
+        if eliminations.is_empty() {
+            return None;
+        }
+
+        return Some(Step {
+            technique: Technique::XChain,
+            digits: vec![digit],
+            cells: path.clone(),
+            units: Vec::new(),
+            placements: Vec::new(),
+            eliminations,
+        });
+    }
+
+    // Links alternate strong, weak, strong, ...; any two cells sharing a unit are weakly
+    // linked.
+    let next: Vec<usize> = if made.is_multiple_of(2) {
+        strong[last].clone()
+    } else {
+        grid::peers(last)
+            .filter(|&cell| board.has(cell, digit))
+            .collect()
+    };
+
+    for cell in next {
+        if path.contains(&cell) {
+            continue;
+        }
+
+        path.push(cell);
+        if let Some(step) = extend_x_chain(board, digit, strong, path, links) {
+            return Some(step);
+        }
+        path.pop();
+    }
+    None
+}
+
+/// A chain of bivalue cells, each sharing a digit with the next: if the first cell is not
+/// `x`, the last one is, so cells seeing both ends cannot be `x`.
+fn xy_chain(board: &Board) -> Option<Step> {
+    for length in 3..=MAX_CHAIN {
+        for start in bivalue_cells(board) {
+            for digit in digits(board.candidates(start)) {
+                // Assume the start is not `digit`; it must then be its other candidate.
+                let value = other(board, start, digit);
+                let mut path = vec![start];
+                if let Some(step) = extend_xy_chain(board, digit, value, &mut path, length) {
+                    return Some(step);
+                }
+            }
+        }
+    }
+    None
+}
+
+fn extend_xy_chain(
+    board: &Board,
+    digit: u8,
+    value: u8,
+    path: &mut Vec<usize>,
+    length: usize,
+) -> Option<Step> {
+    let last = *path.last()?;
+
+    if path.len() == length {
+        if value != digit {
+            return None;
+        }
+
+        let eliminations = eliminate_seen_by(board, digit, &[path[0], last], path);
+        if eliminations.is_empty() {
+            return None;
+        }
+
+        return Some(Step {
+            technique: Technique::XyChain,
+            digits: vec![digit],
+            cells: path.clone(),
+            units: Vec::new(),
+            placements: Vec::new(),
+            eliminations,
+        });
+    }
+
+    let next: Vec<usize> = bivalue_cells(board)
+        .filter(|&cell| grid::sees(last, cell) && board.has(cell, value))
+        .collect();
+
+    for cell in next {
+        if path.contains(&cell) {
+            continue;
+        }
+
+        path.push(cell);
+        let next_value = other(board, cell, value);
+        if let Some(step) = extend_xy_chain(board, digit, next_value, path, length) {
+            return Some(step);
+        }
+        path.pop();
+    }
+    None
+}
+
+/// Boxes first, since hidden singles in a box are the easiest to spot.
+fn box_first_units() -> impl Iterator<Item = Unit> {
+    (0..9).map(Unit::Box).chain(lines())
+}
+
+fn lines() -> impl Iterator<Item = Unit> {
+    (0..9).map(Unit::Row).chain((0..9).map(Unit::Column))
+}
+
+/// The rows and columns containing every cell in `cells`.
+fn shared_lines(cells: &[usize]) -> Vec<Unit> {
+    let mut lines = Vec::new();
+    if cells
+        .iter()
+        .all(|&cell| grid::row(cell) == grid::row(cells[0]))
+    {
+        lines.push(Unit::Row(grid::row(cells[0])));
+    }
+    if cells
+        .iter()
+        .all(|&cell| grid::column(cell) == grid::column(cells[0]))
+    {
+        lines.push(Unit::Column(grid::column(cells[0])));
+    }
+    lines
+}
+
+fn bivalue_cells(board: &Board) -> impl Iterator<Item = usize> + '_ {
+    (0..CELLS).filter(move |&cell| board.candidates(cell).count_ones() == 2)
+}
+
+/// The candidate of a bivalue cell which is not `digit`.
+fn other(board: &Board, cell: usize, digit: u8) -> u8 {
+    digits(board.candidates(cell) & !bit(digit))
+        .next()
+        .unwrap_or(digit)
+}
+
+/// For each digit, the pairs of cells which are the only two places for it in some unit.
+fn strong_links(board: &Board, digit: u8) -> Vec<Vec<usize>> {
+    let mut links = vec![Vec::new(); CELLS];
+    for unit in Unit::all() {
+        if let [a, b] = board.cells_with(unit, digit)[..] {
+            if !links[a].contains(&b) {
+                links[a].push(b);
+                links[b].push(a);
+            }
+        }
+    }
+    links
+}
+
+/// Remove `digit` from `cells`, except from the cells of the pattern itself.
+fn eliminate(
+    board: &Board,
+    digit: u8,
+    cells: impl Iterator<Item = usize>,
+    pattern: &[usize],
+) -> Vec<Candidate> {
+    cells
+        .filter(|cell| !pattern.contains(cell) && board.has(*cell, digit))
+        .map(|cell| Candidate { cell, digit })
+        .collect()
+}
+
+/// Remove `digit` from every cell which sees all of `seen`, except the cells of the pattern.
+fn eliminate_seen_by(
+    board: &Board,
+    digit: u8,
+    seen: &[usize],
+    pattern: &[usize],
+) -> Vec<Candidate> {
+    eliminate(
+        board,
+        digit,
+        (0..CELLS).filter(|&cell| seen.iter().all(|&other| grid::sees(cell, other))),
+        pattern,
+    )
+}
+
+/// Every combination of `size` items, in order.
+fn combinations(items: &[usize], size: usize) -> Vec<Vec<usize>> {
+    if size == 0 {
+        return vec![Vec::new()];
+    }
+
+    let mut result = Vec::new();
+    for (idx, &item) in items.iter().enumerate() {
+        for mut rest in combinations(&items[idx + 1..], size - 1) {
+            rest.insert(0, item);
+            result.push(rest);
+        }
+    }
+    result
+}
@@ -0,0 +1,104 @@
+use std::{
+    fmt::{self, Display},
+    fs::OpenOptions,
+    io::{self, BufWriter, Write},
+    path::{Path, PathBuf},
+};
+
+/// The destination of a written puzzle.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Output {
+    Stdout,
+    File(PathBuf),
+}
+
+impl Output {
+    /// Resolve a user-supplied output path.
+    ///
+    /// No path means `file_name` in the current directory, `-` means standard output, and a
+    /// directory means `file_name` inside that directory.
+    pub fn resolve(path: Option<&str>, file_name: &str) -> Self {
+        match path {
+            None => Output::File(PathBuf::from(file_name)),
+            Some("-") => Output::Stdout,
+            Some(path) if is_dir(path) => Output::File(Path::new(path).join(file_name)),
+            Some(path) => Output::File(PathBuf::from(path)),
+        }
+    }
+
+    /// Whether several puzzles written to `path` share one stream (stdout or a single file)
+    /// rather than each getting its own file.
+    pub fn is_shared(path: Option<&str>) -> bool {
+        match path {
+            None => false,
+            Some(path) => path == "-" || !is_dir(path),
+        }
+    }
+
+    /// Open the output for writing.
+    ///
+    /// Existing files are only replaced when `force` is set; otherwise opening fails with
+    /// `io::ErrorKind::AlreadyExists`.
+    pub fn open(&self, force: bool) -> io::Result<Box<dyn Write>> {
+        match self {
+            Output::Stdout => Ok(Box::new(io::stdout())),
+            Output::File(path) => {
+                let mut options = OpenOptions::new();
+                options.write(true);
+                if force {
+                    options.create(true).truncate(true);
+                } else {
+                    options.create_new(true);
+                }
+
+                let file = options.open(path).map_err(|e| match e.kind() {
+                    io::ErrorKind::AlreadyExists => {
+                        io::Error::new(e.kind(), format!("{} already exists", path.display()))
+                    }
+                    _ => e,
+                })?;
+                Ok(Box::new(BufWriter::new(file)))
+            }
+        }
+    }
+}
+
+impl Display for Output {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Output::Stdout => f.write_str("<stdout>"),
+            Output::File(path) => path.display().fmt(f),
+        }
+    }
+}
+
+fn is_dir(path: &str) -> bool {
+    path.ends_with(std::path::is_separator) || Path::new(path).is_dir()
+}
+
+#[cfg(test)]
+mod test {
+    use std::{env, path::PathBuf};
+
+    use super::Output;
+
+    #[test]
+    fn resolve_paths() {
+        let name = "Hard 1234.csv";
+        let dir = env::temp_dir();
+
+        assert_eq!(
+            Output::resolve(None, name),
+            Output::File(PathBuf::from("Hard 1234.csv"))
+        );
+        assert_eq!(Output::resolve(Some("-"), name), Output::Stdout);
+        assert_eq!(
+            Output::resolve(dir.to_str(), name),
+            Output::File(dir.join("Hard 1234.csv"))
+        );
+        assert_eq!(
+            Output::resolve(Some("puzzle.csv"), name),
+            Output::File(PathBuf::from("puzzle.csv"))
+        );
+    }
+}
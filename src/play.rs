@@ -0,0 +1,287 @@
+//! The state of a puzzle being solved by a player, with undo history.
+//!
+//! A [`Game`] knows nothing about terminals or keys; front ends move its cursor and edit the
+//! cell under it.
+
+use crate::{error::Result, grid, verify::Verification, Puzzle};
+
+/// A puzzle being solved: the player's entries and pencil marks, and a cursor.
+#[derive(Clone, Debug)]
+pub struct Game {
+    puzzle: Puzzle,
+    /// The digit in each cell, givens included, or `0` for blanks.
+    entries: Vec<u8>,
+    /// The pencil marks in each cell, as bits `1 << digit`.
+    marks: Vec<u16>,
+    cursor: usize,
+    undo: Vec<Change>,
+    redo: Vec<Change>,
+}
+
+/// The state of a cell before and after an edit.
+#[derive(Copy, Clone, Debug)]
+struct Edit {
+    cell: usize,
+    before: (u8, u16),
+    after: (u8, u16),
+}
+
+/// One move by the player, which may touch several cells: placing a digit also erases the
+/// pencil marks it rules out.
+#[derive(Clone, Debug)]
+struct Change {
+    cursor: usize,
+    edits: Vec<Edit>,
+}
+
+impl Game {
+    /// Start a game with only the givens filled in and the cursor on the first blank cell.
+    ///
+    /// Fails unless the puzzle passes [`Puzzle::validate`].
+    pub fn new(puzzle: Puzzle) -> Result<Self> {
+        puzzle.validate()?;
+        let entries = puzzle.givens();
+        let cursor = entries.iter().position(|&value| value == 0).unwrap_or(0);
+        Ok(Self {
+            puzzle,
+            entries,
+            marks: vec![0; grid::CELLS],
+            cursor,
+            undo: Vec::new(),
+            redo: Vec::new(),
+        })
+    }
+
+    pub fn puzzle(&self) -> &Puzzle {
+        &self.puzzle
+    }
+
+    /// The digit in every cell, givens included, with `0` for blanks.
+    pub fn entries(&self) -> &[u8] {
+        &self.entries
+    }
+
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    pub fn is_given(&self, cell: usize) -> bool {
+        !self.puzzle.mask[cell]
+    }
+
+    pub fn has_mark(&self, cell: usize, digit: u8) -> bool {
+        self.marks[cell] & 1 << digit != 0
+    }
+
+    /// Move the cursor by whole rows and columns, wrapping around the edges of the grid.
+    pub fn move_cursor(&mut self, rows: isize, columns: isize) {
+        let row = (grid::row(self.cursor) as isize + rows).rem_euclid(9) as usize;
+        let column = (grid::column(self.cursor) as isize + columns).rem_euclid(9) as usize;
+        self.cursor = row * 9 + column;
+    }
+
+    /// Put a digit in the cell under the cursor, or take it out again if it is already there.
+    ///
+    /// Placing a digit erases it from the pencil marks of every cell the cursor sees.
+    /// Returns whether anything changed; givens cannot be edited.
+    pub fn enter(&mut self, digit: u8) -> bool {
+        let cell = self.cursor;
+        if self.is_given(cell) {
+            return false;
+        }
+
+        if self.entries[cell] == digit {
+            return self.apply(vec![(cell, (0, self.marks[cell]))]);
+        }
+
+        let mut states = vec![(cell, (digit, self.marks[cell]))];
+        for peer in grid::peers(cell).filter(|&peer| self.has_mark(peer, digit)) {
+            states.push((peer, (self.entries[peer], self.marks[peer] & !(1 << digit))));
+        }
+        self.apply(states)
+    }
+
+    /// Add or remove a pencil mark in the cell under the cursor, if it holds no digit.
+    pub fn toggle_mark(&mut self, digit: u8) -> bool {
+        let cell = self.cursor;
+        if self.entries[cell] != 0 {
+            return false;
+        }
+        self.apply(vec![(cell, (0, self.marks[cell] ^ 1 << digit))])
+    }
+
+    /// Erase the digit and pencil marks in the cell under the cursor.
+    pub fn clear(&mut self) -> bool {
+        let cell = self.cursor;
+        if self.is_given(cell) {
+            return false;
+        }
+        self.apply(vec![(cell, (0, 0))])
+    }
+
+    /// Take back the last change, moving the cursor to where it was made.
+    pub fn undo(&mut self) -> bool {
+        match self.undo.pop() {
+            Some(change) => {
+                for edit in change.edits.iter().rev() {
+                    self.set(edit.cell, edit.before);
+                }
+                self.cursor = change.cursor;
+                self.redo.push(change);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Make the last change taken back again.
+    pub fn redo(&mut self) -> bool {
+        match self.redo.pop() {
+            Some(change) => {
+                for edit in &change.edits {
+                    self.set(edit.cell, edit.after);
+                }
+                self.cursor = change.cursor;
+                self.undo.push(change);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Whether each cell holds a digit repeated within its row, column or box.
+    ///
+    /// Unlike [`check`](Self::check), this does not consult the solution.
+    pub fn conflicts(&self) -> Vec<bool> {
+        let mut conflicts = vec![false; grid::CELLS];
+        for conflict in self.check().conflicts {
+            for cell in conflict.cells {
+                conflicts[cell] = true;
+            }
+        }
+        conflicts
+    }
+
+    /// Compare the entries with the solution.
+    pub fn check(&self) -> Verification {
+        Verification::new(&self.puzzle, &self.entries).expect("the puzzle has 81 cells")
+    }
+
+    pub fn is_solved(&self) -> bool {
+        self.entries == self.puzzle.solution
+    }
+
+    /// Set cells to new states as one undoable change, unless none of them differ.
+    fn apply(&mut self, states: Vec<(usize, (u8, u16))>) -> bool {
+        let edits: Vec<Edit> = states
+            .into_iter()
+            .map(|(cell, after)| Edit {
+                cell,
+                before: (self.entries[cell], self.marks[cell]),
+                after,
+            })
+            .filter(|edit| edit.before != edit.after)
+            .collect();
+        if edits.is_empty() {
+            return false;
+        }
+
+        for edit in &edits {
+            self.set(edit.cell, edit.after);
+        }
+        self.undo.push(Change {
+            cursor: self.cursor,
+            edits,
+        });
+        self.redo.clear();
+        true
+    }
+
+    fn set(&mut self, cell: usize, (entry, marks): (u8, u16)) {
+        self.entries[cell] = entry;
+        self.marks[cell] = marks;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Game;
+    use crate::fixture;
+
+    fn game() -> Game {
+        Game::new(fixture::puzzle()).unwrap()
+    }
+
+    #[test]
+    fn edits_and_history() {
+        let mut game = game();
+        assert_eq!(game.cursor(), 0);
+
+        // Givens cannot be edited.
+        game.move_cursor(0, 4);
+        assert!(!game.enter(5));
+
+        // Entering a digit erases the marks it rules out in other cells.
+        game.move_cursor(0, -1);
+        assert_eq!(game.cursor(), 3);
+        assert!(game.toggle_mark(2));
+        game.move_cursor(0, -2);
+        assert!(game.enter(2));
+        assert!(!game.has_mark(3, 2));
+        assert_eq!(game.entries()[1], 2);
+
+        assert!(game.undo());
+        assert_eq!(game.entries()[1], 0);
+        assert!(game.has_mark(3, 2));
+        assert!(game.undo());
+        assert!(!game.has_mark(3, 2));
+        assert_eq!(game.cursor(), 3);
+        assert!(!game.undo());
+
+        assert!(game.redo());
+        assert!(game.redo());
+        assert_eq!(game.entries()[1], 2);
+        assert!(!game.redo());
+
+        // A new change forgets what was undone.
+        game.undo();
+        game.move_cursor(0, 2);
+        assert!(game.enter(8));
+        assert!(!game.redo());
+    }
+
+    #[test]
+    fn rejects_invalid_puzzles() {
+        let mut puzzle = fixture::puzzle();
+        puzzle.mask.truncate(80);
+        assert!(Game::new(puzzle).is_err());
+    }
+
+    #[test]
+    fn moves_wrap_around() {
+        let mut game = game();
+        game.move_cursor(-1, -1);
+        assert_eq!(game.cursor(), 80);
+        game.move_cursor(1, 1);
+        assert_eq!(game.cursor(), 0);
+    }
+
+    #[test]
+    fn conflicts_and_check() {
+        let mut game = game();
+
+        // r1c1 is 9; a 7 there repeats the given 7 in r1c5.
+        game.enter(7);
+        let conflicts = game.conflicts();
+        assert!(conflicts[0] && conflicts[4]);
+        assert_eq!(conflicts.iter().filter(|&&conflict| conflict).count(), 2);
+
+        let check = game.check();
+        assert_eq!(check.wrong.len(), 1);
+        assert_eq!(check.missing.len(), 45);
+
+        game.enter(9);
+        assert!(game.conflicts().iter().all(|&conflict| !conflict));
+        assert!(!game.is_solved());
+    }
+}
@@ -0,0 +1,253 @@
+use std::io::{self, Write};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::{Error, Field, Result},
+    format,
+    grid::{self, Unit},
+    solver, Difficulty, PuzzleRef,
+};
+
+/// A websudoku puzzle.
+///
+/// `solution` holds the completed grid in row-major order and `mask` marks which of those
+/// cells are left blank for the player (`true` means the cell is editable).
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Puzzle {
+    pub difficulty: Difficulty,
+    pub id: String,
+    pub solution: Vec<u8>,
+    pub mask: Vec<bool>,
+}
+
+impl Puzzle {
+    /// Build a puzzle from its givens alone (`0` for blanks), solving them for the solution.
+    ///
+    /// `id` must be numeric, as for [`validate`](Self::validate), and the givens must have
+    /// exactly one solution.
+    pub fn from_givens(difficulty: Difficulty, id: String, givens: &[u8]) -> Result<Self> {
+        check_id(&id)?;
+        if givens.len() != grid::CELLS {
+            return Err(Error::InvalidInput(format!(
+                "grid has {} cells; expected 81",
+                givens.len()
+            )));
+        }
+
+        match solver::count_solutions(givens, 2) {
+            0 => return Err(Error::Unsolvable),
+            1 => {}
+            count => return Err(Error::MultipleSolutions(count)),
+        }
+
+        Ok(Puzzle {
+            difficulty,
+            id,
+            solution: solver::solve(givens).ok_or(Error::Unsolvable)?,
+            mask: givens.iter().map(|&value| value == 0).collect(),
+        })
+    }
+
+    /// A reference to the page this puzzle was downloaded from.
+    pub fn puzzle_ref(&self) -> PuzzleRef {
+        PuzzleRef {
+            difficulty: self.difficulty,
+            id: self.id.clone(),
+        }
+    }
+
+    /// Check that the puzzle is a legal, completed sudoku with a numeric identifier.
+    ///
+    /// `id` must hold only digits, both `solution` and `mask` must have 81 cells, every cell
+    /// of `solution` must hold a digit from 1 to 9, and no row, column or box may repeat a
+    /// digit.
+    pub fn validate(&self) -> Result<()> {
+        check_id(&self.id)?;
+
+        if self.solution.len() != grid::CELLS {
+            return Err(Error::CellCount {
+                field: Field::Solution,
+                count: self.solution.len(),
+            });
+        }
+
+        if self.mask.len() != grid::CELLS {
+            return Err(Error::CellCount {
+                field: Field::Mask,
+                count: self.mask.len(),
+            });
+        }
+
+        if let Some((index, &digit)) = self
+            .solution
+            .iter()
+            .enumerate()
+            .find(|(_, &digit)| !(1..=9).contains(&digit))
+        {
+            return Err(Error::DigitOutOfRange { index, digit });
+        }
+
+        for unit in Unit::all() {
+            let mut seen = [false; 10];
+            for cell in unit.cells().iter() {
+                let digit = self.solution[*cell];
+                if seen[digit as usize] {
+                    return Err(Error::Conflict { unit, digit });
+                }
+                seen[digit as usize] = true;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Solve the givens independently and check the result against `solution`.
+    ///
+    /// A mismatch means the page was corrupted or tampered with, or that the givens do not
+    /// have a unique solution.
+    pub fn cross_check(&self) -> Result<()> {
+        let solved = solver::solve(&self.givens()).ok_or(Error::Unsolvable)?;
+        match solved
+            .iter()
+            .zip(&self.solution)
+            .position(|(expected, found)| expected != found)
+        {
+            Some(index) => Err(Error::SolutionMismatch {
+                index,
+                expected: solved[index],
+                found: self.solution[index],
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Count the solutions of the givens, stopping once `limit` solutions are found.
+    pub fn count_solutions(&self, limit: usize) -> usize {
+        solver::count_solutions(&self.givens(), limit)
+    }
+
+    /// The puzzle as presented to the player: the solution with editable cells set to `0`.
+    pub fn givens(&self) -> Vec<u8> {
+        self.solution
+            .iter()
+            .zip(&self.mask)
+            .map(|(&value, &can_edit)| if can_edit { 0 } else { value })
+            .collect()
+    }
+
+    /// The default name of a file containing this puzzle, e.g. `Evil 7042100266.csv`.
+    pub fn file_name(&self, extension: &str) -> String {
+        format!("{} {}.{}", self.difficulty, self.id, extension)
+    }
+
+    /// Write the puzzle's givens as nine rows of nine comma separated fields, leaving
+    /// editable cells blank.
+    ///
+    /// The puzzle should be checked with [`Puzzle::validate`] first; a grid without 81
+    /// cells fails before anything is written. See [`Format::Csv`](crate::Format::Csv) for a header,
+    /// blank placeholders or the solution.
+    pub fn write_masked_puzzle(&self, w: impl Write) -> io::Result<()> {
+        format::csv::write(self, &format::Options::default(), w)
+    }
+}
+
+/// Check that a puzzle identifier is a number. Identifiers end up in file names, so
+/// nothing else is accepted.
+pub(crate) fn check_id(id: &str) -> Result<()> {
+    if id.is_empty() {
+        return Err(Error::MissingField(Field::PuzzleId));
+    }
+    match id.char_indices().find(|(_, c)| !c.is_ascii_digit()) {
+        Some((index, value)) => Err(Error::MalformedDigit {
+            field: Field::PuzzleId,
+            index,
+            value,
+        }),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Puzzle;
+    use crate::{fixture::puzzle, grid::Unit, Difficulty, Error, Field};
+
+    #[test]
+    fn from_givens_solves_for_solution() {
+        let puzzle = puzzle();
+        let solved = Puzzle::from_givens(puzzle.difficulty, puzzle.id.clone(), &puzzle.givens());
+        assert_eq!(solved.unwrap(), puzzle);
+        assert!(matches!(
+            Puzzle::from_givens(Difficulty::Easy, String::from("1"), &[0; 81]),
+            Err(Error::MultipleSolutions(2))
+        ));
+        assert!(matches!(
+            Puzzle::from_givens(Difficulty::Easy, String::from("stdin"), &puzzle.givens()),
+            Err(Error::MalformedDigit {
+                field: Field::PuzzleId,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn validate_accepts_legal_solution() {
+        assert!(puzzle().validate().is_ok());
+    }
+
+    #[test]
+    fn cross_check_flags_tampering() {
+        let mut puzzle = puzzle();
+        assert!(puzzle.cross_check().is_ok());
+
+        // Swapping two editable digits within a row keeps every given intact.
+        puzzle.solution.swap(0, 1);
+        assert!(matches!(
+            puzzle.cross_check(),
+            Err(Error::SolutionMismatch {
+                index: 0,
+                expected: 9,
+                found: 8
+            })
+        ));
+    }
+
+    #[test]
+    fn validate_rejects_bad_grids() {
+        let mut short = puzzle();
+        short.solution.pop();
+        assert!(matches!(
+            short.validate(),
+            Err(Error::CellCount { count: 80, .. })
+        ));
+
+        let mut zero = puzzle();
+        zero.solution[3] = 0;
+        assert!(matches!(
+            zero.validate(),
+            Err(Error::DigitOutOfRange { index: 3, digit: 0 })
+        ));
+
+        let mut path = puzzle();
+        path.id = String::from("1/../2");
+        assert!(matches!(
+            path.validate(),
+            Err(Error::MalformedDigit {
+                field: Field::PuzzleId,
+                index: 1,
+                value: '/'
+            })
+        ));
+
+        let mut swapped = puzzle();
+        swapped.solution.swap(0, 9);
+        assert!(matches!(
+            swapped.validate(),
+            Err(Error::Conflict {
+                unit: Unit::Row(0),
+                digit: 7
+            })
+        ));
+    }
+}
@@ -0,0 +1,183 @@
+//! Page geometry shared by the graphical output formats.
+//!
+//! A [`Layout`] describes where the grid lines and digits of a puzzle go, in whatever unit
+//! the output uses (pixels for svg, points for pdf). Renderers only decide how to draw them.
+
+use crate::{grid, Puzzle};
+
+/// The size and style of a rendered grid.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Layout {
+    /// The width and height of one cell.
+    pub cell_size: f32,
+    /// The space around the grid.
+    pub margin: f32,
+    /// The width of the lines between cells.
+    pub thin_line: f32,
+    /// The width of the lines around boxes and the grid.
+    pub thick_line: f32,
+    /// The font family used for digits, for formats which name fonts.
+    pub font_family: String,
+    /// The height of a digit's font as a fraction of the cell size.
+    pub font_scale: f32,
+}
+
+impl Default for Layout {
+    fn default() -> Self {
+        Self {
+            cell_size: 40.0,
+            margin: 10.0,
+            thin_line: 1.0,
+            thick_line: 3.0,
+            font_family: String::from("sans-serif"),
+            font_scale: 0.6,
+        }
+    }
+}
+
+/// A filled rectangle: one grid line.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Rect {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+/// A digit to draw in a cell.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Glyph {
+    pub cell: usize,
+    pub digit: u8,
+    pub bold: bool,
+}
+
+impl Layout {
+    /// The width and height of the grid, including its margin.
+    pub fn size(&self) -> f32 {
+        9.0 * self.cell_size + 2.0 * self.margin
+    }
+
+    /// The same layout with every length multiplied by `factor`.
+    pub fn scaled(&self, factor: f32) -> Layout {
+        Layout {
+            cell_size: self.cell_size * factor,
+            margin: self.margin * factor,
+            thin_line: self.thin_line * factor,
+            thick_line: self.thick_line * factor,
+            ..self.clone()
+        }
+    }
+
+    pub fn font_size(&self) -> f32 {
+        self.cell_size * self.font_scale
+    }
+
+    /// Every grid line, thin lines first so that thick lines are drawn over them.
+    pub fn lines(&self) -> Vec<Rect> {
+        let mut lines = Vec::new();
+        for &thick in &[false, true] {
+            for idx in (0..=9).filter(|idx| (idx % 3 == 0) == thick) {
+                let width = if thick {
+                    self.thick_line
+                } else {
+                    self.thin_line
+                };
+                let offset = self.margin + idx as f32 * self.cell_size - width / 2.0;
+
+                // Lines run past the outer grid lines' centres so that corners are square.
+                let start = self.margin - self.thick_line / 2.0;
+                let length = 9.0 * self.cell_size + self.thick_line;
+
+                lines.push(Rect {
+                    x: offset,
+                    y: start,
+                    width,
+                    height: length,
+                });
+                lines.push(Rect {
+                    x: start,
+                    y: offset,
+                    width: length,
+                    height: width,
+                });
+            }
+        }
+        lines
+    }
+
+    /// The top left corner of a cell.
+    pub fn origin(&self, cell: usize) -> (f32, f32) {
+        (
+            self.margin + grid::column(cell) as f32 * self.cell_size,
+            self.margin + grid::row(cell) as f32 * self.cell_size,
+        )
+    }
+
+    /// Where to put the baseline of a digit `width` wide so that it sits centred in a cell.
+    ///
+    /// Digits are assumed to be about 0.72 of the font size tall, as in most sans-serif
+    /// fonts.
+    pub fn baseline(&self, cell: usize, width: f32) -> (f32, f32) {
+        let (x, y) = self.origin(cell);
+        (
+            x + (self.cell_size - width) / 2.0,
+            y + (self.cell_size + 0.72 * self.font_size()) / 2.0,
+        )
+    }
+}
+
+/// The digits to draw for a puzzle.
+///
+/// Only givens are drawn unless `with_solution` is set, in which case every cell is filled
+/// from the solution and `bold_givens` picks out the givens.
+pub fn glyphs(puzzle: &Puzzle, with_solution: bool, bold_givens: bool) -> Vec<Glyph> {
+    puzzle
+        .solution
+        .iter()
+        .zip(&puzzle.mask)
+        .enumerate()
+        .filter(|(_, (_, &can_edit))| with_solution || !can_edit)
+        .map(|(cell, (&digit, &can_edit))| Glyph {
+            cell,
+            digit,
+            bold: with_solution && bold_givens && !can_edit,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{glyphs, Layout};
+    use crate::fixture;
+
+    #[test]
+    fn lines_cover_the_grid() {
+        let layout = Layout::default();
+        let lines = layout.lines();
+
+        assert_eq!(lines.len(), 20);
+        assert_eq!(layout.size(), 380.0);
+
+        // The left border is thick and spans the whole grid.
+        let border = lines
+            .iter()
+            .find(|line| line.x == 8.5 && line.height > line.width)
+            .unwrap();
+        assert_eq!(border.width, 3.0);
+        assert_eq!(border.height, 363.0);
+        assert_eq!(lines.iter().filter(|line| line.width == 1.0).count(), 6);
+    }
+
+    #[test]
+    fn glyph_modes() {
+        let puzzle = fixture::puzzle();
+
+        assert_eq!(glyphs(&puzzle, false, true).len(), 35);
+        assert!(glyphs(&puzzle, false, true).iter().all(|glyph| !glyph.bold));
+
+        let solution = glyphs(&puzzle, true, true);
+        assert_eq!(solution.len(), 81);
+        assert_eq!(solution.iter().filter(|glyph| glyph.bold).count(), 35);
+    }
+}
@@ -0,0 +1,212 @@
+use std::fmt::{self, Display};
+
+use regex::Regex;
+
+use crate::{
+    error::{Error, Result},
+    puzzle, Difficulty,
+};
+
+/// A reference to a single websudoku puzzle: its difficulty level and identifier.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PuzzleRef {
+    pub difficulty: Difficulty,
+    pub id: String,
+}
+
+impl PuzzleRef {
+    /// The url of the websudoku page hosting this puzzle.
+    pub fn url(&self) -> String {
+        format!(
+            "https://grid.websudoku.com/?level={}&set_id={}",
+            self.difficulty.level(),
+            self.id
+        )
+    }
+}
+
+impl Display for PuzzleRef {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.difficulty, self.id)
+    }
+}
+
+/// The most puzzles a single range may name, so that a typo cannot queue billions of
+/// downloads.
+const MAX_RANGE: u64 = 10_000;
+
+/// Resolves user input (a bare puzzle id or a websudoku url) to a `PuzzleRef`.
+pub struct Resolver {
+    id_pattern: Regex,
+    difficulty_pattern: Regex,
+    range_pattern: Regex,
+}
+
+impl Resolver {
+    pub fn new() -> Self {
+        Self {
+            id_pattern: Regex::new(r#"set_id=(\d+)"#).unwrap(),
+            difficulty_pattern: Regex::new(r#"level=(\d)"#).unwrap(),
+            range_pattern: Regex::new(r#"^(\d+)\.\.(=?)(\d+)$"#).unwrap(),
+        }
+    }
+
+    /// Resolve a puzzle url or identifier.
+    ///
+    /// A difficulty level embedded in a url always wins; otherwise `difficulty` is used,
+    /// falling back to the default difficulty. Identifiers must be numeric, so that a stray
+    /// argument such as a file name is not mistaken for a puzzle.
+    pub fn resolve(&self, puzzle: &str, difficulty: Option<Difficulty>) -> Result<PuzzleRef> {
+        let id = match self.id_pattern.captures(puzzle) {
+            Some(captures) => captures
+                .get(1)
+                .expect("Non-optional capture group should not fail")
+                .as_str()
+                .to_string(),
+            None => puzzle.replace(',', ""),
+        };
+        puzzle::check_id(&id).map_err(|_| {
+            Error::InvalidInput(format!("not a puzzle url, identifier or range: {}", puzzle))
+        })?;
+
+        let difficulty = match self.difficulty_pattern.captures(puzzle) {
+            None => difficulty.unwrap_or_default(),
+            Some(captures) => Difficulty::from_level(
+                captures
+                    .get(1)
+                    .expect("Non-optional capture group should not fail")
+                    .as_str(),
+            ),
+        };
+
+        Ok(PuzzleRef { difficulty, id })
+    }
+
+    /// Resolve a puzzle url, identifier or range of identifiers.
+    ///
+    /// Ranges follow Rust syntax: `1000..1100` excludes its upper bound and `1000..=1100`
+    /// includes it. Every puzzle in a range shares the same difficulty, and a range may name
+    /// at most 10,000 puzzles.
+    pub fn expand(&self, puzzle: &str, difficulty: Option<Difficulty>) -> Result<Vec<PuzzleRef>> {
+        let spec = puzzle.replace(',', "");
+        let captures = match self.range_pattern.captures(&spec) {
+            Some(captures) => captures,
+            None => return Ok(vec![self.resolve(puzzle, difficulty)?]),
+        };
+
+        let bound = |idx: usize| {
+            captures[idx]
+                .parse::<u64>()
+                .map_err(|_| Error::InvalidInput(format!("range bound too large: {}", puzzle)))
+        };
+        let start = bound(1)?;
+        let end = match &captures[2] {
+            "=" => bound(3)?.saturating_add(1),
+            _ => bound(3)?,
+        };
+
+        if start >= end {
+            return Err(Error::InvalidInput(format!("empty range: {}", puzzle)));
+        }
+        if end - start > MAX_RANGE {
+            return Err(Error::InvalidInput(format!(
+                "range names {} puzzles; at most {} are allowed: {}",
+                end - start,
+                MAX_RANGE,
+                puzzle
+            )));
+        }
+
+        let difficulty = difficulty.unwrap_or_default();
+        Ok((start..end)
+            .map(|id| PuzzleRef {
+                difficulty,
+                id: id.to_string(),
+            })
+            .collect())
+    }
+}
+
+impl Default for Resolver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{PuzzleRef, Resolver};
+    use crate::{Difficulty, Error};
+
+    #[test]
+    fn resolve_prefers_url_level() {
+        let resolver = Resolver::new();
+        let actual = resolver
+            .resolve(
+                "https://grid.websudoku.com/?level=2&set_id=7042100266",
+                Some(Difficulty::Hard),
+            )
+            .unwrap();
+        let expected = PuzzleRef {
+            difficulty: Difficulty::Medium,
+            id: String::from("7042100266"),
+        };
+
+        assert_eq!(actual, expected);
+        assert_eq!(
+            actual.url(),
+            "https://grid.websudoku.com/?level=2&set_id=7042100266"
+        );
+    }
+
+    #[test]
+    fn resolve_bare_id() {
+        let resolver = Resolver::new();
+        let actual = resolver.resolve("7,042,100,266", None).unwrap();
+
+        assert_eq!(actual.difficulty, Difficulty::Evil);
+        assert_eq!(actual.id, "7042100266");
+    }
+
+    #[test]
+    fn rejects_non_numeric_ids() {
+        let resolver = Resolver::new();
+
+        assert!(matches!(
+            resolver.resolve("out.csv", None),
+            Err(Error::InvalidInput(_))
+        ));
+        assert!(matches!(
+            resolver.expand("out.csv", None),
+            Err(Error::InvalidInput(_))
+        ));
+        assert!(resolver.expand("", None).is_err());
+    }
+
+    #[test]
+    fn expand_ranges() {
+        let resolver = Resolver::new();
+        let ids = |spec| {
+            resolver
+                .expand(spec, Some(Difficulty::Easy))
+                .unwrap()
+                .into_iter()
+                .map(|puzzle| puzzle.id)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(ids("1000..1003"), ["1000", "1001", "1002"]);
+        assert_eq!(ids("1,000..=1,002"), ["1000", "1001", "1002"]);
+        assert_eq!(ids("1000"), ["1000"]);
+        assert!(resolver.expand("1000..1000", None).is_err());
+    }
+
+    #[test]
+    fn rejects_oversized_ranges() {
+        let resolver = Resolver::new();
+
+        assert_eq!(resolver.expand("1..=10000", None).unwrap().len(), 10_000);
+        assert!(resolver.expand("1..100000000000", None).is_err());
+        assert!(resolver.expand("0..=18446744073709551615", None).is_err());
+    }
+}
@@ -0,0 +1,165 @@
+//! A backtracking solver used to check puzzles independently of websudoku's own solution.
+
+use crate::grid::{self, CELLS};
+
+/// Solve a grid of givens, where `0` marks a blank cell.
+///
+/// Returns the first solution found, or `None` if the givens cannot be completed. Grids
+/// with fewer or more than 81 cells, or with values above 9, have no solution.
+pub fn solve(givens: &[u8]) -> Option<Vec<u8>> {
+    let mut solution = None;
+    search(givens, |grid| {
+        solution = Some(grid.to_vec());
+        false
+    });
+    solution
+}
+
+/// Count the solutions of a grid of givens, stopping once `limit` solutions are found.
+///
+/// A puzzle is proper when this returns exactly 1 for any `limit` of 2 or more.
+pub fn count_solutions(givens: &[u8], limit: usize) -> usize {
+    let mut count = 0;
+    if limit > 0 {
+        search(givens, |_| {
+            count += 1;
+            count < limit
+        });
+    }
+    count
+}
+
+/// Call `f` with each solution of `givens` until it returns `false` or the search is done.
+fn search(givens: &[u8], mut f: impl FnMut(&[u8]) -> bool) {
+    let mut state = match State::new(givens) {
+        Some(state) => state,
+        None => return,
+    };
+    state.search(&mut f);
+}
+
+struct State {
+    grid: [u8; CELLS],
+    rows: [u16; 9],
+    columns: [u16; 9],
+    boxes: [u16; 9],
+}
+
+impl State {
+    fn new(givens: &[u8]) -> Option<Self> {
+        if givens.len() != CELLS {
+            return None;
+        }
+
+        let mut state = State {
+            grid: [0; CELLS],
+            rows: [0; 9],
+            columns: [0; 9],
+            boxes: [0; 9],
+        };
+
+        for (cell, &value) in givens.iter().enumerate() {
+            match value {
+                0 => {}
+                1..=9 if state.candidates(cell) & bit(value) != 0 => state.place(cell, value),
+                _ => return None,
+            }
+        }
+
+        Some(state)
+    }
+
+    /// The digits which may be placed in `cell`, as a bit set.
+    fn candidates(&self, cell: usize) -> u16 {
+        !(self.rows[grid::row(cell)]
+            | self.columns[grid::column(cell)]
+            | self.boxes[grid::box_of(cell)])
+            & 0x3fe
+    }
+
+    fn place(&mut self, cell: usize, value: u8) {
+        self.grid[cell] = value;
+        self.toggle(cell, value);
+    }
+
+    fn clear(&mut self, cell: usize) {
+        let value = self.grid[cell];
+        self.grid[cell] = 0;
+        self.toggle(cell, value);
+    }
+
+    fn toggle(&mut self, cell: usize, value: u8) {
+        self.rows[grid::row(cell)] ^= bit(value);
+        self.columns[grid::column(cell)] ^= bit(value);
+        self.boxes[grid::box_of(cell)] ^= bit(value);
+    }
+
+    /// Returns `false` once the search should stop.
+    fn search(&mut self, f: &mut impl FnMut(&[u8]) -> bool) -> bool {
+        // Branch on the blank cell with the fewest candidates.
+        let mut best: Option<(usize, u16)> = None;
+        for cell in (0..CELLS).filter(|&cell| self.grid[cell] == 0) {
+            let candidates = self.candidates(cell);
+            if best.is_none_or(|(_, best)| candidates.count_ones() < best.count_ones()) {
+                best = Some((cell, candidates));
+                if candidates.count_ones() <= 1 {
+                    break;
+                }
+            }
+        }
+
+        let (cell, candidates) = match best {
+            Some(best) => best,
+            None => return f(&self.grid),
+        };
+
+        for value in 1..=9 {
+            if candidates & bit(value) != 0 {
+                self.place(cell, value);
+                let go_on = self.search(f);
+                self.clear(cell);
+                if !go_on {
+                    return false;
+                }
+            }
+        }
+
+        true
+    }
+}
+
+fn bit(value: u8) -> u16 {
+    1 << value
+}
+
+#[cfg(test)]
+mod test {
+    use super::{count_solutions, solve};
+    use crate::fixture;
+
+    #[test]
+    fn solves_sample() {
+        let puzzle = fixture::puzzle();
+        let givens = puzzle.givens();
+
+        assert_eq!(solve(&givens), Some(puzzle.solution));
+        assert_eq!(count_solutions(&givens, 10), 1);
+    }
+
+    #[test]
+    fn counts_up_to_limit() {
+        let empty = vec![0; 81];
+        assert_eq!(count_solutions(&empty, 0), 0);
+        assert_eq!(count_solutions(&empty, 5), 5);
+    }
+
+    #[test]
+    fn rejects_contradictions() {
+        let mut givens = vec![0; 81];
+        givens[0] = 5;
+        givens[1] = 5;
+
+        assert_eq!(solve(&givens), None);
+        assert_eq!(solve(&givens[..80]), None);
+    }
+}
@@ -0,0 +1,260 @@
+//! Checking a player's answer against a puzzle's solution.
+
+use std::io::{self, Write};
+
+use serde::Serialize;
+
+use crate::{
+    error::{Error, Field, Result},
+    grid::{self, Unit},
+    Difficulty, Puzzle,
+};
+
+/// A cell holding the wrong digit.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Mistake {
+    pub cell: usize,
+    pub expected: u8,
+    pub found: u8,
+}
+
+/// A digit repeated within one unit of the answer.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Conflict {
+    pub unit: Unit,
+    pub digit: u8,
+    pub cells: Vec<usize>,
+}
+
+/// Everything wrong with an answer; an empty verification means the puzzle is solved.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Verification {
+    pub wrong: Vec<Mistake>,
+    /// Blank cells, in row-major order.
+    pub missing: Vec<usize>,
+    /// Repeated digits. Every conflict involves at least one wrong cell, but they are
+    /// usually the easier mistake to find.
+    pub conflicts: Vec<Conflict>,
+}
+
+impl Verification {
+    /// Compare an answer (`0` for blanks) with the puzzle's solution.
+    ///
+    /// Fails unless both the answer and the solution have 81 cells.
+    pub fn new(puzzle: &Puzzle, answer: &[u8]) -> Result<Self> {
+        if puzzle.solution.len() != grid::CELLS {
+            return Err(Error::CellCount {
+                field: Field::Solution,
+                count: puzzle.solution.len(),
+            });
+        }
+        if answer.len() != grid::CELLS {
+            return Err(Error::InvalidInput(format!(
+                "answer has {} cells; expected 81",
+                answer.len()
+            )));
+        }
+
+        let mut verification = Verification::default();
+
+        for (cell, (&found, &expected)) in answer.iter().zip(&puzzle.solution).enumerate() {
+            if found == 0 {
+                verification.missing.push(cell);
+            } else if found != expected {
+                verification.wrong.push(Mistake {
+                    cell,
+                    expected,
+                    found,
+                });
+            }
+        }
+
+        for unit in Unit::all() {
+            for digit in 1..=9 {
+                let cells: Vec<usize> = unit
+                    .cells()
+                    .iter()
+                    .copied()
+                    .filter(|&cell| answer[cell] == digit)
+                    .collect();
+                if cells.len() > 1 {
+                    verification.conflicts.push(Conflict { unit, digit, cells });
+                }
+            }
+        }
+
+        Ok(verification)
+    }
+
+    pub fn is_solved(&self) -> bool {
+        self.wrong.is_empty() && self.missing.is_empty()
+    }
+
+    /// Write a summary line for the puzzle, followed by one indented line per problem.
+    pub fn write_text(&self, puzzle: &Puzzle, mut w: impl Write) -> io::Result<()> {
+        if self.is_solved() {
+            return writeln!(w, "{}: solved", puzzle.puzzle_ref());
+        }
+
+        writeln!(
+            w,
+            "{}: {} wrong, {} missing, {} conflicts",
+            puzzle.puzzle_ref(),
+            self.wrong.len(),
+            self.missing.len(),
+            self.conflicts.len()
+        )?;
+        for mistake in &self.wrong {
+            writeln!(
+                w,
+                "  wrong: {} holds {}; expected {}",
+                grid::cell_name(mistake.cell),
+                mistake.found,
+                mistake.expected
+            )?;
+        }
+        if !self.missing.is_empty() {
+            writeln!(w, "  missing: {}", cell_names(&self.missing).join(", "))?;
+        }
+        for conflict in &self.conflicts {
+            writeln!(
+                w,
+                "  conflict: {} repeated in {} ({})",
+                conflict.digit,
+                conflict.unit,
+                cell_names(&conflict.cells).join(", ")
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Write the verification as one line of JSON.
+    pub fn write_json(&self, puzzle: &Puzzle, mut w: impl Write) -> io::Result<()> {
+        serde_json::to_writer(&mut w, &Document::new(puzzle, self))?;
+        writeln!(w)
+    }
+}
+
+fn cell_names(cells: &[usize]) -> Vec<String> {
+    cells.iter().map(|&cell| grid::cell_name(cell)).collect()
+}
+
+/// The JSON representation of a verification, naming cells `r1c1` to `r9c9`.
+#[derive(Serialize)]
+struct Document<'a> {
+    id: &'a str,
+    difficulty: Difficulty,
+    solved: bool,
+    wrong: Vec<MistakeDocument>,
+    missing: Vec<String>,
+    conflicts: Vec<ConflictDocument>,
+}
+
+#[derive(Serialize)]
+struct MistakeDocument {
+    cell: String,
+    expected: u8,
+    found: u8,
+}
+
+#[derive(Serialize)]
+struct ConflictDocument {
+    unit: String,
+    digit: u8,
+    cells: Vec<String>,
+}
+
+impl<'a> Document<'a> {
+    fn new(puzzle: &'a Puzzle, verification: &Verification) -> Self {
+        Self {
+            id: &puzzle.id,
+            difficulty: puzzle.difficulty,
+            solved: verification.is_solved(),
+            wrong: verification
+                .wrong
+                .iter()
+                .map(|mistake| MistakeDocument {
+                    cell: grid::cell_name(mistake.cell),
+                    expected: mistake.expected,
+                    found: mistake.found,
+                })
+                .collect(),
+            missing: cell_names(&verification.missing),
+            conflicts: verification
+                .conflicts
+                .iter()
+                .map(|conflict| ConflictDocument {
+                    unit: conflict.unit.to_string(),
+                    digit: conflict.digit,
+                    cells: cell_names(&conflict.cells),
+                })
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Conflict, Mistake, Verification};
+    use crate::{fixture::puzzle, grid::Unit, Error};
+
+    #[test]
+    fn correct_answer_is_solved() {
+        let puzzle = puzzle();
+        assert!(Verification::new(&puzzle, &puzzle.solution)
+            .unwrap()
+            .is_solved());
+    }
+
+    #[test]
+    fn rejects_grids_without_81_cells() {
+        let mut puzzle = puzzle();
+        assert!(matches!(
+            Verification::new(&puzzle, &puzzle.solution[..80]),
+            Err(Error::InvalidInput(_))
+        ));
+
+        let answer = puzzle.solution.clone();
+        puzzle.solution.truncate(72);
+        assert!(matches!(
+            Verification::new(&puzzle, &answer),
+            Err(Error::CellCount { count: 72, .. })
+        ));
+    }
+
+    #[test]
+    fn reports_wrong_missing_and_conflicting_cells() {
+        let puzzle = puzzle();
+        let mut answer = puzzle.solution.clone();
+        answer[0] = 4;
+        answer[80] = 0;
+
+        let verification = Verification::new(&puzzle, &answer).unwrap();
+
+        assert!(!verification.is_solved());
+        assert_eq!(
+            verification.wrong,
+            [Mistake {
+                cell: 0,
+                expected: 9,
+                found: 4
+            }]
+        );
+        assert_eq!(verification.missing, [80]);
+        assert_eq!(
+            verification.conflicts[0],
+            Conflict {
+                unit: Unit::Row(0),
+                digit: 4,
+                cells: vec![0, 2]
+            }
+        );
+
+        let mut json = Vec::new();
+        verification.write_json(&puzzle, &mut json).unwrap();
+        let value: serde_json::Value = serde_json::from_slice(&json).unwrap();
+        assert_eq!(value["solved"], false);
+        assert_eq!(value["wrong"][0]["cell"], "r1c1");
+        assert_eq!(value["missing"][0], "r9c9");
+    }
+}